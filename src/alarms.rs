@@ -0,0 +1,133 @@
+//! Bindings to the `alarms` API.
+
+use crate::EventTarget;
+use js_sys::{Array, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/alarms/#type-Alarm
+    #[derive(Debug, Clone)]
+    pub type Alarm;
+
+    // Name of this alarm.
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &Alarm) -> String;
+
+    // Time at which this alarm was scheduled to fire, in milliseconds past the epoch.
+    #[wasm_bindgen(method, getter, js_name = scheduledTime)]
+    pub fn scheduled_time(this: &Alarm) -> f64;
+
+    // If not null, the alarm is a repeating alarm and will fire again in `period_in_minutes` minutes.
+    #[wasm_bindgen(method, getter, js_name = periodInMinutes)]
+    pub fn period_in_minutes(this: &Alarm) -> Option<f64>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/alarms/#type-AlarmCreateInfo
+    /// When an alarm created by [`Alarms::create`] fires.
+    ///
+    /// Either [`when`](Self::when) or [`delay_in_minutes`](Self::delay_in_minutes)
+    /// must be set. With [`period_in_minutes`](Self::period_in_minutes) the alarm repeats.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type AlarmCreateInfo;
+
+    #[wasm_bindgen(method, setter = delayInMinutes)]
+    fn set_delay_in_minutes(this: &AlarmCreateInfo, value: f64);
+
+    #[wasm_bindgen(method, setter = periodInMinutes)]
+    fn set_period_in_minutes(this: &AlarmCreateInfo, value: f64);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_when(this: &AlarmCreateInfo, value: f64);
+}
+
+impl AlarmCreateInfo {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Minutes from now until the alarm fires for the first time.
+    pub fn delay_in_minutes(&mut self, value: f64) -> &mut Self {
+        self.set_delay_in_minutes(value);
+        self
+    }
+
+    /// Minutes between two firings of a repeating alarm.
+    pub fn period_in_minutes(&mut self, value: f64) -> &mut Self {
+        self.set_period_in_minutes(value);
+        self
+    }
+
+    /// Time at which the alarm fires, in milliseconds past the epoch.
+    pub fn when(&mut self, value: f64) -> &mut Self {
+        self.set_when(value);
+        self
+    }
+}
+
+impl Default for AlarmCreateInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/alarms/
+    pub type Alarms;
+
+    // Creates an alarm. An existing alarm with the same name is replaced.
+    #[wasm_bindgen(catch, method)]
+    pub async fn create(
+        this: &Alarms,
+        name: Option<&str>,
+        info: &AlarmCreateInfo,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = get)]
+    async fn get_js(this: &Alarms, name: Option<&str>) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAll)]
+    async fn get_all_js(this: &Alarms) -> Result<Array, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = clear)]
+    async fn clear_js(this: &Alarms, name: Option<&str>) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = clearAll)]
+    async fn clear_all_js(this: &Alarms) -> Result<JsValue, JsValue>;
+
+    // Fired when an alarm has elapsed. The listener receives the `Alarm`.
+    #[wasm_bindgen(method, getter, js_name = onAlarm)]
+    pub fn on_alarm(this: &Alarms) -> EventTarget;
+}
+
+impl Alarms {
+    /// Retrieves details about the alarm with the given name, or the unnamed alarm.
+    pub async fn get(&self, name: Option<&str>) -> Result<Option<Alarm>, JsValue> {
+        let alarm = self.get_js(name).await?;
+        Ok((!alarm.is_undefined()).then(|| alarm.unchecked_into()))
+    }
+
+    /// Retrieves all alarms.
+    pub async fn get_all(&self) -> Result<Vec<Alarm>, JsValue> {
+        let alarms = self.get_all_js().await?;
+        Ok(alarms.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    /// Clears the alarm with the given name, or the unnamed alarm.
+    ///
+    /// Returns whether an alarm was cleared.
+    pub async fn clear(&self, name: Option<&str>) -> Result<bool, JsValue> {
+        Ok(self.clear_js(name).await?.is_truthy())
+    }
+
+    /// Clears all alarms.
+    ///
+    /// Returns whether any alarm was cleared.
+    pub async fn clear_all(&self) -> Result<bool, JsValue> {
+        Ok(self.clear_all_js().await?.is_truthy())
+    }
+}
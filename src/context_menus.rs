@@ -0,0 +1,773 @@
+//! Bindings to the `contextMenus` API.
+
+use crate::{api, Browser, EventTarget, ListenerHandle, Tab};
+use js_sys::{Array, Function, Object, Promise};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/#type-ContextType
+    /// The different contexts a menu can appear in.
+    ContextType {
+        All = "all",
+        Page = "page",
+        Frame = "frame",
+        Selection = "selection",
+        Link = "link",
+        Editable = "editable",
+        Image = "image",
+        Video = "video",
+        Audio = "audio",
+        Launcher = "launcher",
+        BrowserAction = "browser_action",
+        PageAction = "page_action",
+        Action = "action",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/#type-ItemType
+    /// The type of menu item.
+    ItemType {
+        Normal = "normal",
+        Checkbox = "checkbox",
+        Radio = "radio",
+        Separator = "separator",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/#type-OnClickData
+    #[derive(Debug, Clone)]
+    pub type OnClickData;
+
+    // The ID of the menu item that was clicked, a string or a number.
+    #[wasm_bindgen(method, getter, js_name = menuItemId)]
+    pub fn menu_item_id(this: &OnClickData) -> JsValue;
+
+    // The parent ID, if any, for the item clicked.
+    #[wasm_bindgen(method, getter, js_name = parentMenuItemId)]
+    pub fn parent_menu_item_id(this: &OnClickData) -> JsValue;
+
+    // One of "image", "video", or "audio" if the context menu was activated on one of these types of elements.
+    #[wasm_bindgen(method, getter, js_name = mediaType)]
+    pub fn media_type(this: &OnClickData) -> Option<String>;
+
+    // If the element is a link, the URL it points to.
+    #[wasm_bindgen(method, getter, js_name = linkUrl)]
+    pub fn link_url(this: &OnClickData) -> Option<String>;
+
+    // Will be present for elements with a 'src' URL.
+    #[wasm_bindgen(method, getter, js_name = srcUrl)]
+    pub fn src_url(this: &OnClickData) -> Option<String>;
+
+    // The URL of the page where the menu item was clicked.
+    #[wasm_bindgen(method, getter, js_name = pageUrl)]
+    pub fn page_url(this: &OnClickData) -> Option<String>;
+
+    // The URL of the frame of the element where the context menu was clicked, if it was in a frame.
+    #[wasm_bindgen(method, getter, js_name = frameUrl)]
+    pub fn frame_url(this: &OnClickData) -> Option<String>;
+
+    // The ID of the frame of the element where the context menu was clicked, if it was in a frame.
+    #[wasm_bindgen(method, getter, js_name = frameId)]
+    pub fn frame_id(this: &OnClickData) -> Option<u32>;
+
+    // The text for the context selection, if any.
+    #[wasm_bindgen(method, getter, js_name = selectionText)]
+    pub fn selection_text(this: &OnClickData) -> Option<String>;
+
+    // A flag indicating whether the element is editable (text input, textarea, etc.).
+    #[wasm_bindgen(method, getter)]
+    pub fn editable(this: &OnClickData) -> bool;
+
+    // A flag indicating the state of a checkbox or radio item before it was clicked.
+    #[wasm_bindgen(method, getter, js_name = wasChecked)]
+    pub fn was_checked(this: &OnClickData) -> Option<bool>;
+
+    // A flag indicating the state of a checkbox or radio item after it is clicked.
+    #[wasm_bindgen(method, getter)]
+    pub fn checked(this: &OnClickData) -> Option<bool>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/#method-create
+    /// Properties of the menu item created by [`ContextMenus::create`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type ContextMenuCreateProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_id(this: &ContextMenuCreateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &ContextMenuCreateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter = type)]
+    fn set_item_type(this: &ContextMenuCreateProperties, value: JsValue);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_contexts(this: &ContextMenuCreateProperties, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_checked(this: &ContextMenuCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_enabled(this: &ContextMenuCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_visible(this: &ContextMenuCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = parentId)]
+    fn set_parent_id(this: &ContextMenuCreateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter = documentUrlPatterns)]
+    fn set_document_url_patterns(this: &ContextMenuCreateProperties, value: &Array);
+
+    #[wasm_bindgen(method, setter = targetUrlPatterns)]
+    fn set_target_url_patterns(this: &ContextMenuCreateProperties, value: &Array);
+}
+
+impl ContextMenuCreateProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The unique ID of the menu item.
+    pub fn id(&mut self, value: &str) -> &mut Self {
+        self.set_id(value);
+        self
+    }
+
+    /// The text to display in the item. `%s` is replaced with the selected text.
+    pub fn title(&mut self, value: &str) -> &mut Self {
+        self.set_title(value);
+        self
+    }
+
+    /// The type of the menu item, defaults to `normal`.
+    pub fn item_type(&mut self, value: ItemType) -> &mut Self {
+        self.set_item_type(value.into());
+        self
+    }
+
+    /// The contexts the menu item appears in, defaults to `page`.
+    pub fn contexts(&mut self, value: &[ContextType]) -> &mut Self {
+        self.set_contexts(&value.iter().cloned().map(JsValue::from).collect());
+        self
+    }
+
+    /// The initial state of a checkbox or radio item.
+    pub fn checked(&mut self, value: bool) -> &mut Self {
+        self.set_checked(value);
+        self
+    }
+
+    /// Whether the menu item is enabled.
+    pub fn enabled(&mut self, value: bool) -> &mut Self {
+        self.set_enabled(value);
+        self
+    }
+
+    /// Whether the menu item is shown in the menu.
+    pub fn visible(&mut self, value: bool) -> &mut Self {
+        self.set_visible(value);
+        self
+    }
+
+    /// The ID of the parent menu item.
+    pub fn parent_id(&mut self, value: &str) -> &mut Self {
+        self.set_parent_id(value);
+        self
+    }
+
+    /// Restricts the item to documents whose URL matches one of the patterns.
+    pub fn document_url_patterns<S: AsRef<str>>(&mut self, value: &[S]) -> &mut Self {
+        self.set_document_url_patterns(
+            &value
+                .iter()
+                .map(|value| JsValue::from_str(value.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Restricts the item to links and media whose URL matches one of the patterns.
+    pub fn target_url_patterns<S: AsRef<str>>(&mut self, value: &[S]) -> &mut Self {
+        self.set_target_url_patterns(
+            &value
+                .iter()
+                .map(|value| JsValue::from_str(value.as_ref()))
+                .collect(),
+        );
+        self
+    }
+}
+
+impl Default for ContextMenuCreateProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/#method-update
+    /// Properties changed by [`ContextMenus::update`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type ContextMenuUpdateProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &ContextMenuUpdateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter = type)]
+    fn set_item_type(this: &ContextMenuUpdateProperties, value: JsValue);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_contexts(this: &ContextMenuUpdateProperties, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_checked(this: &ContextMenuUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_enabled(this: &ContextMenuUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_visible(this: &ContextMenuUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = parentId)]
+    fn set_parent_id(this: &ContextMenuUpdateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter = documentUrlPatterns)]
+    fn set_document_url_patterns(this: &ContextMenuUpdateProperties, value: &Array);
+
+    #[wasm_bindgen(method, setter = targetUrlPatterns)]
+    fn set_target_url_patterns(this: &ContextMenuUpdateProperties, value: &Array);
+}
+
+impl ContextMenuUpdateProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The text to display in the item. `%s` is replaced with the selected text.
+    pub fn title(&mut self, value: &str) -> &mut Self {
+        self.set_title(value);
+        self
+    }
+
+    /// The type of the menu item, defaults to `normal`.
+    pub fn item_type(&mut self, value: ItemType) -> &mut Self {
+        self.set_item_type(value.into());
+        self
+    }
+
+    /// The contexts the menu item appears in, defaults to `page`.
+    pub fn contexts(&mut self, value: &[ContextType]) -> &mut Self {
+        self.set_contexts(&value.iter().cloned().map(JsValue::from).collect());
+        self
+    }
+
+    /// The initial state of a checkbox or radio item.
+    pub fn checked(&mut self, value: bool) -> &mut Self {
+        self.set_checked(value);
+        self
+    }
+
+    /// Whether the menu item is enabled.
+    pub fn enabled(&mut self, value: bool) -> &mut Self {
+        self.set_enabled(value);
+        self
+    }
+
+    /// Whether the menu item is shown in the menu.
+    pub fn visible(&mut self, value: bool) -> &mut Self {
+        self.set_visible(value);
+        self
+    }
+
+    /// The ID of the parent menu item.
+    pub fn parent_id(&mut self, value: &str) -> &mut Self {
+        self.set_parent_id(value);
+        self
+    }
+
+    /// Restricts the item to documents whose URL matches one of the patterns.
+    pub fn document_url_patterns<S: AsRef<str>>(&mut self, value: &[S]) -> &mut Self {
+        self.set_document_url_patterns(
+            &value
+                .iter()
+                .map(|value| JsValue::from_str(value.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Restricts the item to links and media whose URL matches one of the patterns.
+    pub fn target_url_patterns<S: AsRef<str>>(&mut self, value: &[S]) -> &mut Self {
+        self.set_target_url_patterns(
+            &value
+                .iter()
+                .map(|value| JsValue::from_str(value.as_ref()))
+                .collect(),
+        );
+        self
+    }
+}
+
+impl Default for ContextMenuUpdateProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/contextMenus/
+    #[derive(Debug, Clone)]
+    pub type ContextMenus;
+
+    #[wasm_bindgen(method, getter, js_name = ACTION_MENU_TOP_LEVEL_LIMIT)]
+    pub fn action_menu_top_level_limit(this: &ContextMenus) -> u32;
+
+    // Creates a new menu item and returns its ID.
+    #[wasm_bindgen(method)]
+    pub fn create(this: &ContextMenus, properties: &ContextMenuCreateProperties) -> JsValue;
+
+    #[wasm_bindgen(catch, method, js_name = create)]
+    fn create_with_callback(
+        this: &ContextMenus,
+        properties: &ContextMenuCreateProperties,
+        callback: &Function,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn update(
+        this: &ContextMenus,
+        id: &JsValue,
+        properties: &ContextMenuUpdateProperties,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn remove(this: &ContextMenus, id: &JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = removeAll)]
+    pub async fn remove_all(this: &ContextMenus) -> Result<(), JsValue>;
+
+    // Fired when a menu item is clicked. The listener receives `OnClickData` and an optional `Tab`.
+    #[wasm_bindgen(method, getter, js_name = onClicked)]
+    pub fn on_clicked(this: &ContextMenus) -> EventTarget;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str, error: &JsValue);
+}
+
+impl ContextMenus {
+    /// Creates a new menu item and returns its ID once the browser created it.
+    ///
+    /// Unlike [`ContextMenus::create`], fails with the error the browser
+    /// reports, e.g. for an unknown parent or an invalid context.
+    pub async fn try_create(
+        &self,
+        properties: &ContextMenuCreateProperties,
+    ) -> Result<JsValue, JsValue> {
+        let mut id = Ok(JsValue::UNDEFINED);
+        let created = Promise::new(&mut |resolve, reject| {
+            let callback = Closure::once_into_js(move || {
+                let _ = match api().runtime().last_error() {
+                    Some(error) => reject.call1(&JsValue::NULL, &error),
+                    None => resolve.call0(&JsValue::NULL),
+                };
+            });
+            id = self.create_with_callback(properties, callback.unchecked_ref());
+        });
+        let id = id?;
+        JsFuture::from(created).await?;
+        Ok(id)
+    }
+}
+
+type ClickHandler = dyn Fn(&OnClickData, Option<&Tab>);
+
+/// An item of a [`MenuTree`].
+pub struct MenuItem {
+    id: String,
+    title: Option<String>,
+    item_type: Option<ItemType>,
+    contexts: Vec<ContextType>,
+    checked: Option<bool>,
+    enabled: Option<bool>,
+    visible: Option<bool>,
+    document_url_patterns: Vec<String>,
+    target_url_patterns: Vec<String>,
+    children: Vec<MenuItem>,
+    on_click: Option<Rc<ClickHandler>>,
+}
+
+impl MenuItem {
+    /// A menu item with an ID that is unique within its [`MenuTree`].
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: Some(title.into()),
+            item_type: None,
+            contexts: Vec::new(),
+            checked: None,
+            enabled: None,
+            visible: None,
+            document_url_patterns: Vec::new(),
+            target_url_patterns: Vec::new(),
+            children: Vec::new(),
+            on_click: None,
+        }
+    }
+
+    /// A separator line.
+    pub fn separator(id: impl Into<String>) -> Self {
+        Self {
+            title: None,
+            ..Self::new(id, "")
+        }
+        .item_type(ItemType::Separator)
+    }
+
+    pub fn item_type(mut self, item_type: ItemType) -> Self {
+        self.item_type = Some(item_type);
+        self
+    }
+
+    /// The contexts the item appears in. Children don't inherit them.
+    pub fn contexts(mut self, contexts: &[ContextType]) -> Self {
+        self.contexts = contexts.to_vec();
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = Some(visible);
+        self
+    }
+
+    pub fn document_url_patterns<S: Into<String>>(
+        mut self,
+        patterns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.document_url_patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn target_url_patterns<S: Into<String>>(
+        mut self,
+        patterns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.target_url_patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds a submenu item.
+    pub fn child(mut self, child: MenuItem) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Called when this item is clicked.
+    pub fn on_click(mut self, handler: impl Fn(&OnClickData, Option<&Tab>) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Appends the properties of this item and its descendants, parents first.
+    fn create_properties(
+        &self,
+        parent_id: Option<&str>,
+        list: &mut Vec<ContextMenuCreateProperties>,
+    ) {
+        let mut properties = ContextMenuCreateProperties::new();
+        properties.id(&self.id);
+        if let Some(title) = &self.title {
+            properties.title(title);
+        }
+        if let Some(item_type) = &self.item_type {
+            properties.item_type(item_type.clone());
+        }
+        if !self.contexts.is_empty() {
+            properties.contexts(&self.contexts);
+        }
+        if let Some(checked) = self.checked {
+            properties.checked(checked);
+        }
+        if let Some(enabled) = self.enabled {
+            properties.enabled(enabled);
+        }
+        if let Some(visible) = self.visible {
+            properties.visible(visible);
+        }
+        if let Some(parent_id) = parent_id {
+            properties.parent_id(parent_id);
+        }
+        if !self.document_url_patterns.is_empty() {
+            properties.document_url_patterns(&self.document_url_patterns);
+        }
+        if !self.target_url_patterns.is_empty() {
+            properties.target_url_patterns(&self.target_url_patterns);
+        }
+        list.push(properties);
+        for child in &self.children {
+            child.create_properties(Some(&self.id), list);
+        }
+    }
+
+    fn check_ids<'a>(&'a self, ids: &mut HashSet<&'a str>) -> Result<(), DuplicateMenuItemId> {
+        if !ids.insert(&self.id) {
+            return Err(DuplicateMenuItemId(self.id.clone()));
+        }
+        self.children
+            .iter()
+            .try_for_each(|child| child.check_ids(ids))
+    }
+
+    fn collect_handlers(&self, handlers: &mut HashMap<String, Rc<ClickHandler>>) {
+        if let Some(on_click) = &self.on_click {
+            handlers.insert(self.id.clone(), Rc::clone(on_click));
+        }
+        for child in &self.children {
+            child.collect_handlers(handlers);
+        }
+    }
+}
+
+impl fmt::Debug for MenuItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MenuItem")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("item_type", &self.item_type)
+            .field("contexts", &self.contexts)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two items of a [`MenuTree`] have the same id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateMenuItemId(pub String);
+
+impl fmt::Display for DuplicateMenuItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the menu item id {:?} is not unique", self.0)
+    }
+}
+
+impl std::error::Error for DuplicateMenuItemId {}
+
+impl From<DuplicateMenuItemId> for JsValue {
+    fn from(error: DuplicateMenuItemId) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
+
+/// A declarative context menu.
+///
+/// [`MenuTree::register`] creates the items whenever the extension is
+/// installed or updated and sends each click to the handler of the item
+/// that was clicked.
+#[derive(Debug, Default)]
+pub struct MenuTree {
+    items: Vec<MenuItem>,
+}
+
+type InstalledListener = dyn FnMut(JsValue);
+type ClickedListener = dyn FnMut(OnClickData, Option<Tab>);
+
+impl MenuTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a top-level item.
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Fails if two items of the tree have the same id.
+    pub fn check_ids(&self) -> Result<(), DuplicateMenuItemId> {
+        let mut ids = HashSet::new();
+        self.items
+            .iter()
+            .try_for_each(|item| item.check_ids(&mut ids))
+    }
+
+    /// Replaces all menu items of the extension with the items of this tree.
+    ///
+    /// Fails without touching the menu if two items have the same id, and
+    /// with the first error the browser reports while creating the items.
+    pub async fn install(&self, menus: &ContextMenus) -> Result<(), JsValue> {
+        self.check_ids()?;
+        let mut list = Vec::new();
+        for item in &self.items {
+            item.create_properties(None, &mut list);
+        }
+        menus.remove_all().await?;
+        for properties in &list {
+            menus.try_create(properties).await?;
+        }
+        Ok(())
+    }
+
+    /// Registers the `runtime.onInstalled` and `contextMenus.onClicked` listeners.
+    ///
+    /// In a service worker this must be called synchronously at startup,
+    /// so that clicks which wake up the worker are delivered. Fails if two
+    /// items have the same id, as the clicks could not be told apart.
+    pub fn register(self, browser: &Browser) -> Result<MenuTreeRegistration, DuplicateMenuItemId> {
+        self.check_ids()?;
+        let menus = browser.context_menus();
+        let mut handlers = HashMap::new();
+        for item in &self.items {
+            item.collect_handlers(&mut handlers);
+        }
+        let tree = Rc::new(self);
+        let installed = {
+            let menus = menus.clone();
+            Closure::<InstalledListener>::new(move |_details| {
+                let tree = Rc::clone(&tree);
+                let menus = menus.clone();
+                spawn_local(async move {
+                    // There is nobody to return the error to, and a failed
+                    // installation leaves the menu incomplete until the next update.
+                    if let Err(error) = tree.install(&menus).await {
+                        console_error("failed to install the context menu:", &error);
+                    }
+                });
+            })
+        };
+        let clicked =
+            Closure::<ClickedListener>::new(move |info: OnClickData, tab: Option<Tab>| {
+                let handler = info
+                    .menu_item_id()
+                    .as_string()
+                    .and_then(|id| handlers.get(&id));
+                if let Some(handler) = handler {
+                    handler(&info, tab.as_ref());
+                }
+            });
+        Ok(MenuTreeRegistration {
+            _installed: browser.runtime().on_installed().listen(installed),
+            _clicked: menus.on_clicked().listen(clicked),
+        })
+    }
+}
+
+/// The listeners registered by [`MenuTree::register`].
+///
+/// Dropping it removes the listeners.
+#[must_use = "dropping a `MenuTreeRegistration` removes its listeners"]
+#[derive(Debug)]
+pub struct MenuTreeRegistration {
+    _installed: ListenerHandle<InstalledListener>,
+    _clicked: ListenerHandle<ClickedListener>,
+}
+
+impl MenuTreeRegistration {
+    /// Keeps the listeners registered for the rest of the program.
+    pub fn detach(self) {
+        self._installed.detach();
+        self._clicked.detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_ids() {
+        let tree = MenuTree::new()
+            .item(MenuItem::new("a", "A").child(MenuItem::new("b", "B")))
+            .item(MenuItem::new("c", "C"));
+        assert_eq!(tree.check_ids(), Ok(()));
+
+        let tree = tree.item(MenuItem::new("d", "D").child(MenuItem::new("b", "Other B")));
+        assert_eq!(tree.check_ids(), Err(DuplicateMenuItemId("b".into())));
+    }
+
+    // Run with `wasm-pack test --node` or `cargo test --target wasm32-unknown-unknown`.
+    #[cfg(target_arch = "wasm32")]
+    mod wasm {
+        use super::*;
+        use js_sys::Reflect;
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        /// A `contextMenus` namespace that records the ids of the created
+        /// items and reports an error through `runtime.lastError` for the id `fail`.
+        fn context_menus() -> ContextMenus {
+            Function::new_no_args(
+                r#"
+                globalThis.chrome = { runtime: {} };
+                const created = [];
+                return {
+                    created,
+                    removeAll: async () => { created.length = 0; },
+                    create(properties, callback) {
+                        queueMicrotask(() => {
+                            if (properties.id === "fail") {
+                                chrome.runtime.lastError = new Error("invalid item");
+                            } else {
+                                created.push(properties.id);
+                            }
+                            callback();
+                            delete chrome.runtime.lastError;
+                        });
+                        return properties.id;
+                    },
+                };
+                "#,
+            )
+            .call0(&JsValue::NULL)
+            .unwrap()
+            .unchecked_into()
+        }
+
+        fn created(menus: &ContextMenus) -> Vec<String> {
+            let created: Array = Reflect::get(menus, &"created".into())
+                .unwrap()
+                .unchecked_into();
+            created.iter().filter_map(|id| id.as_string()).collect()
+        }
+
+        #[wasm_bindgen_test]
+        async fn install_reports_creation_errors() {
+            let menus = context_menus();
+            let tree = MenuTree::new().item(MenuItem::new("a", "A").child(MenuItem::new("b", "B")));
+            tree.install(&menus).await.unwrap();
+            assert_eq!(created(&menus), ["a", "b"]);
+
+            let tree = tree
+                .item(MenuItem::new("fail", "Fail"))
+                .item(MenuItem::new("c", "C"));
+            assert!(tree.install(&menus).await.is_err());
+            assert_eq!(created(&menus), ["a", "b"]);
+        }
+    }
+}
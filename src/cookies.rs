@@ -0,0 +1,544 @@
+//! Bindings to the `cookies` API.
+
+use crate::{EventTarget, TabId};
+use js_sys::{Array, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-SameSiteStatus
+    /// A cookie's 'SameSite' state.
+    SameSiteStatus {
+        NoRestriction = "no_restriction",
+        Lax = "lax",
+        Strict = "strict",
+        /// The cookie was set without a SameSite attribute.
+        Unspecified = "unspecified",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-OnChangedCause
+    /// The underlying reason behind a cookie's change.
+    OnChangedCause {
+        Evicted = "evicted",
+        Expired = "expired",
+        Explicit = "explicit",
+        ExpiredOverwrite = "expired_overwrite",
+        Overwrite = "overwrite",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-CookiePartitionKey
+    /// The partition key of a partitioned (CHIPS) cookie.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type CookiePartitionKey;
+
+    // The top-level site the partitioned cookie is available in.
+    #[wasm_bindgen(method, getter, js_name = topLevelSite)]
+    pub fn top_level_site(this: &CookiePartitionKey) -> Option<String>;
+
+    // Indicates if the cookie was set in a cross-site context.
+    #[wasm_bindgen(method, getter, js_name = hasCrossSiteAncestor)]
+    pub fn has_cross_site_ancestor(this: &CookiePartitionKey) -> Option<bool>;
+
+    #[wasm_bindgen(method, setter = topLevelSite)]
+    fn set_top_level_site(this: &CookiePartitionKey, value: &str);
+
+    #[wasm_bindgen(method, setter = hasCrossSiteAncestor)]
+    fn set_has_cross_site_ancestor(this: &CookiePartitionKey, value: bool);
+}
+
+impl CookiePartitionKey {
+    /// The partition of cookies set in a context embedded in `top_level_site`.
+    pub fn new(top_level_site: &str) -> Self {
+        let key: Self = Object::new().unchecked_into();
+        key.set_top_level_site(top_level_site);
+        key
+    }
+
+    /// Whether the cookie was set in a cross-site context.
+    pub fn cross_site_ancestor(&mut self, value: bool) -> &mut Self {
+        self.set_has_cross_site_ancestor(value);
+        self
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-Cookie
+    #[derive(Debug, Clone)]
+    pub type Cookie;
+
+    // The name of the cookie.
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &Cookie) -> String;
+
+    // The value of the cookie.
+    #[wasm_bindgen(method, getter)]
+    pub fn value(this: &Cookie) -> String;
+
+    // The domain of the cookie (e.g. "www.google.com", "example.com").
+    #[wasm_bindgen(method, getter)]
+    pub fn domain(this: &Cookie) -> String;
+
+    // True if the cookie is a host-only cookie (i.e. a request's host must exactly match the domain of the cookie).
+    #[wasm_bindgen(method, getter, js_name = hostOnly)]
+    pub fn host_only(this: &Cookie) -> bool;
+
+    // The path of the cookie.
+    #[wasm_bindgen(method, getter)]
+    pub fn path(this: &Cookie) -> String;
+
+    // True if the cookie is marked as Secure.
+    #[wasm_bindgen(method, getter)]
+    pub fn secure(this: &Cookie) -> bool;
+
+    // True if the cookie is marked as HttpOnly.
+    #[wasm_bindgen(method, getter, js_name = httpOnly)]
+    pub fn http_only(this: &Cookie) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = sameSite)]
+    fn same_site_js(this: &Cookie) -> Option<String>;
+
+    // True if the cookie is a session cookie, as opposed to a persistent cookie with an expiration date.
+    #[wasm_bindgen(method, getter)]
+    pub fn session(this: &Cookie) -> bool;
+
+    // The expiration date of the cookie as the number of seconds since the UNIX epoch. Not provided for session cookies.
+    #[wasm_bindgen(method, getter, js_name = expirationDate)]
+    pub fn expiration_date(this: &Cookie) -> Option<f64>;
+
+    // The ID of the cookie store containing this cookie. In Firefox this is the `Tab::cookie_store_id` of container tabs.
+    #[wasm_bindgen(method, getter, js_name = storeId)]
+    pub fn store_id(this: &Cookie) -> String;
+
+    // The partition key for reading or modifying cookies with the Partitioned attribute.
+    #[wasm_bindgen(method, getter, js_name = partitionKey)]
+    pub fn partition_key(this: &Cookie) -> Option<CookiePartitionKey>;
+
+    // The first-party domain of the cookie (Firefox only).
+    #[wasm_bindgen(method, getter, js_name = firstPartyDomain)]
+    pub fn first_party_domain(this: &Cookie) -> Option<String>;
+}
+
+impl Cookie {
+    /// The cookie's same-site status.
+    pub fn same_site(&self) -> SameSiteStatus {
+        self.same_site_js()
+            .map(SameSiteStatus::from)
+            .unwrap_or(SameSiteStatus::Unspecified)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-CookieStore
+    #[derive(Debug, Clone)]
+    pub type CookieStore;
+
+    // The unique identifier for the cookie store, see `Tab::cookie_store_id`.
+    #[wasm_bindgen(method, getter)]
+    pub fn id(this: &CookieStore) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = tabIds)]
+    fn tab_ids_js(this: &CookieStore) -> Array;
+
+    // Whether this is the store of private browsing windows (Firefox only).
+    #[wasm_bindgen(method, getter)]
+    pub fn incognito(this: &CookieStore) -> Option<bool>;
+}
+
+impl CookieStore {
+    /// The tabs that share this cookie store.
+    pub fn tab_ids(&self) -> Vec<TabId> {
+        self.tab_ids_js()
+            .iter()
+            .filter_map(|id| id.as_f64())
+            .map(|id| TabId(id as i32))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#event-onChanged
+    #[derive(Debug, Clone)]
+    pub type CookieChangeInfo;
+
+    // True if a cookie was removed.
+    #[wasm_bindgen(method, getter)]
+    pub fn removed(this: &CookieChangeInfo) -> bool;
+
+    // Information about the cookie that was set or removed.
+    #[wasm_bindgen(method, getter)]
+    pub fn cookie(this: &CookieChangeInfo) -> Cookie;
+
+    #[wasm_bindgen(method, getter, js_name = cause)]
+    fn cause_js(this: &CookieChangeInfo) -> String;
+}
+
+impl CookieChangeInfo {
+    /// The underlying reason behind the cookie's change.
+    pub fn cause(&self) -> OnChangedCause {
+        OnChangedCause::from(self.cause_js())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#type-CookieDetails
+    /// Identifies the cookie read by [`Cookies::get`] or deleted by [`Cookies::remove`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type CookieDetails;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_name(this: &CookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &CookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter = storeId)]
+    fn set_store_id(this: &CookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter = partitionKey)]
+    fn set_partition_key(this: &CookieDetails, value: &CookiePartitionKey);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = firstPartyDomain)]
+    fn set_first_party_domain(this: &CookieDetails, value: &str);
+}
+
+impl CookieDetails {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The name of the cookie.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.set_name(value);
+        self
+    }
+
+    /// The URL with which the cookie is associated.
+    pub fn url(&mut self, value: &str) -> &mut Self {
+        self.set_url(value);
+        self
+    }
+
+    /// The cookie store, e.g. the `Tab::cookie_store_id` of a container tab, defaults to the store of the current context.
+    pub fn store_id(&mut self, value: &str) -> &mut Self {
+        self.set_store_id(value);
+        self
+    }
+
+    /// The partition key of a partitioned (CHIPS) cookie.
+    pub fn partition_key(&mut self, value: &CookiePartitionKey) -> &mut Self {
+        self.set_partition_key(value);
+        self
+    }
+
+    /// The first-party domain of the cookie, required when first-party isolation is enabled (Firefox only).
+    pub fn first_party_domain(&mut self, value: &str) -> &mut Self {
+        self.set_first_party_domain(value);
+        self
+    }
+}
+
+impl Default for CookieDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#method-set
+    /// The cookie written by [`Cookies::set`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type SetCookieDetails;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_name(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_value(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_domain(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_path(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_secure(this: &SetCookieDetails, value: bool);
+
+    #[wasm_bindgen(method, setter = httpOnly)]
+    fn set_http_only(this: &SetCookieDetails, value: bool);
+
+    #[wasm_bindgen(method, setter = sameSite)]
+    fn set_same_site(this: &SetCookieDetails, value: JsValue);
+
+    #[wasm_bindgen(method, setter = expirationDate)]
+    fn set_expiration_date(this: &SetCookieDetails, value: f64);
+
+    #[wasm_bindgen(method, setter = storeId)]
+    fn set_store_id(this: &SetCookieDetails, value: &str);
+
+    #[wasm_bindgen(method, setter = partitionKey)]
+    fn set_partition_key(this: &SetCookieDetails, value: &CookiePartitionKey);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = firstPartyDomain)]
+    fn set_first_party_domain(this: &SetCookieDetails, value: &str);
+}
+
+impl SetCookieDetails {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The URL to associate with the cookie.
+    pub fn url(&mut self, value: &str) -> &mut Self {
+        self.set_url(value);
+        self
+    }
+
+    /// The name of the cookie, empty by default.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.set_name(value);
+        self
+    }
+
+    /// The value of the cookie, empty by default.
+    pub fn value(&mut self, value: &str) -> &mut Self {
+        self.set_value(value);
+        self
+    }
+
+    /// The domain of the cookie; without it the cookie is a host-only cookie.
+    pub fn domain(&mut self, value: &str) -> &mut Self {
+        self.set_domain(value);
+        self
+    }
+
+    /// The path of the cookie, defaults to the path portion of the URL.
+    pub fn path(&mut self, value: &str) -> &mut Self {
+        self.set_path(value);
+        self
+    }
+
+    /// Whether the cookie is marked as Secure.
+    pub fn secure(&mut self, value: bool) -> &mut Self {
+        self.set_secure(value);
+        self
+    }
+
+    /// Whether the cookie is marked as HttpOnly.
+    pub fn http_only(&mut self, value: bool) -> &mut Self {
+        self.set_http_only(value);
+        self
+    }
+
+    /// The cookie's same-site status.
+    pub fn same_site(&mut self, value: SameSiteStatus) -> &mut Self {
+        self.set_same_site(value.into());
+        self
+    }
+
+    /// The expiration date in seconds since the UNIX epoch; without it the cookie is a session cookie.
+    pub fn expiration_date(&mut self, value: f64) -> &mut Self {
+        self.set_expiration_date(value);
+        self
+    }
+
+    /// The cookie store, e.g. the `Tab::cookie_store_id` of a container tab, defaults to the store of the current context.
+    pub fn store_id(&mut self, value: &str) -> &mut Self {
+        self.set_store_id(value);
+        self
+    }
+
+    /// The partition key of a partitioned (CHIPS) cookie.
+    pub fn partition_key(&mut self, value: &CookiePartitionKey) -> &mut Self {
+        self.set_partition_key(value);
+        self
+    }
+
+    /// The first-party domain of the cookie, required when first-party isolation is enabled (Firefox only).
+    pub fn first_party_domain(&mut self, value: &str) -> &mut Self {
+        self.set_first_party_domain(value);
+        self
+    }
+}
+
+impl Default for SetCookieDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/#method-getAll
+    /// Filters the cookies returned by [`Cookies::get_all`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type GetAllCookiesDetails;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_domain(this: &GetAllCookiesDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_name(this: &GetAllCookiesDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_path(this: &GetAllCookiesDetails, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_secure(this: &GetAllCookiesDetails, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_session(this: &GetAllCookiesDetails, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &GetAllCookiesDetails, value: &str);
+
+    #[wasm_bindgen(method, setter = storeId)]
+    fn set_store_id(this: &GetAllCookiesDetails, value: &str);
+
+    #[wasm_bindgen(method, setter = partitionKey)]
+    fn set_partition_key(this: &GetAllCookiesDetails, value: &CookiePartitionKey);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = firstPartyDomain)]
+    fn set_first_party_domain(this: &GetAllCookiesDetails, value: &str);
+}
+
+impl GetAllCookiesDetails {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Restricts the cookies to those whose domains match or are subdomains of this one.
+    pub fn domain(&mut self, value: &str) -> &mut Self {
+        self.set_domain(value);
+        self
+    }
+
+    /// Filters the cookies by name.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.set_name(value);
+        self
+    }
+
+    /// Restricts the cookies to those whose path exactly matches this one.
+    pub fn path(&mut self, value: &str) -> &mut Self {
+        self.set_path(value);
+        self
+    }
+
+    /// Filters the cookies by their Secure property.
+    pub fn secure(&mut self, value: bool) -> &mut Self {
+        self.set_secure(value);
+        self
+    }
+
+    /// Filters out session vs. persistent cookies.
+    pub fn session(&mut self, value: bool) -> &mut Self {
+        self.set_session(value);
+        self
+    }
+
+    /// Restricts the cookies to those that would match the given URL.
+    pub fn url(&mut self, value: &str) -> &mut Self {
+        self.set_url(value);
+        self
+    }
+
+    /// The cookie store, e.g. the `Tab::cookie_store_id` of a container tab, defaults to the store of the current context.
+    pub fn store_id(&mut self, value: &str) -> &mut Self {
+        self.set_store_id(value);
+        self
+    }
+
+    /// The partition key of a partitioned (CHIPS) cookie.
+    pub fn partition_key(&mut self, value: &CookiePartitionKey) -> &mut Self {
+        self.set_partition_key(value);
+        self
+    }
+
+    /// The first-party domain of the cookie, required when first-party isolation is enabled (Firefox only).
+    pub fn first_party_domain(&mut self, value: &str) -> &mut Self {
+        self.set_first_party_domain(value);
+        self
+    }
+}
+
+impl Default for GetAllCookiesDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/cookies/
+    pub type Cookies;
+
+    #[wasm_bindgen(catch, method, js_name = get)]
+    async fn get_js(this: &Cookies, details: &CookieDetails) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAll)]
+    async fn get_all_js(this: &Cookies, details: &GetAllCookiesDetails) -> Result<Array, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = set)]
+    async fn set_js(this: &Cookies, details: &SetCookieDetails) -> Result<JsValue, JsValue>;
+
+    // Deletes a cookie by name and resolves to the details of the removed cookie.
+    #[wasm_bindgen(catch, method)]
+    pub async fn remove(this: &Cookies, details: &CookieDetails) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAllCookieStores)]
+    async fn get_all_cookie_stores_js(this: &Cookies) -> Result<Array, JsValue>;
+
+    // Fired when a cookie is set or removed. The listener receives a `CookieChangeInfo`.
+    #[wasm_bindgen(method, getter, js_name = onChanged)]
+    pub fn on_changed(this: &Cookies) -> EventTarget;
+}
+
+impl Cookies {
+    /// Retrieves the cookie with the longest path and the earliest creation time
+    /// that matches the details.
+    pub async fn get(&self, details: &CookieDetails) -> Result<Option<Cookie>, JsValue> {
+        let cookie = self.get_js(details).await?;
+        Ok((!cookie.is_null() && !cookie.is_undefined()).then(|| cookie.unchecked_into()))
+    }
+
+    /// Retrieves all cookies that match the filter, longest path first.
+    pub async fn get_all(&self, details: &GetAllCookiesDetails) -> Result<Vec<Cookie>, JsValue> {
+        let cookies = self.get_all_js(details).await?;
+        Ok(cookies.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    /// Sets a cookie and returns it, or `None` if it could not be set.
+    pub async fn set(&self, details: &SetCookieDetails) -> Result<Option<Cookie>, JsValue> {
+        let cookie = self.set_js(details).await?;
+        Ok((!cookie.is_null() && !cookie.is_undefined()).then(|| cookie.unchecked_into()))
+    }
+
+    /// Lists all existing cookie stores.
+    pub async fn get_all_cookie_stores(&self) -> Result<Vec<CookieStore>, JsValue> {
+        let stores = self.get_all_cookie_stores_js().await?;
+        Ok(stores.iter().map(JsCast::unchecked_into).collect())
+    }
+}
@@ -0,0 +1,559 @@
+//! Conversion of Adblock Plus network filters into `declarativeNetRequest` rules.
+
+use super::{
+    ruleset_json, DomainType, RequestMethod, Rule, RuleAction, RuleCondition, RuleError,
+    GUARANTEED_MINIMUM_STATIC_RULES, MAX_NUMBER_OF_REGEX_RULES,
+};
+use crate::ResourceType;
+use std::fmt;
+
+/// The priority of rules converted from blocking filters.
+const BLOCK_PRIORITY: u32 = 1;
+/// The priority of rules converted from `@@` exceptions, so they override blocking filters.
+const EXCEPTION_PRIORITY: u32 = 2;
+/// The priority of rules converted from `$important` filters, so they override exceptions.
+const IMPORTANT_PRIORITY: u32 = 3;
+
+/// Options of [`convert_filter_list`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterListOptions {
+    /// The id of the first rule. The following rules are numbered consecutively.
+    pub first_rule_id: u32,
+    /// The maximum number of rules, by default the number of static rules
+    /// every extension can enable.
+    pub max_rules: usize,
+    /// The maximum number of rules with a `regex_filter`.
+    pub max_regex_rules: usize,
+}
+
+impl Default for FilterListOptions {
+    fn default() -> Self {
+        Self {
+            first_rule_id: 1,
+            max_rules: GUARANTEED_MINIMUM_STATIC_RULES,
+            max_regex_rules: MAX_NUMBER_OF_REGEX_RULES,
+        }
+    }
+}
+
+/// The result of [`convert_filter_list`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterListConversion {
+    /// The rules of the converted filters, in the order of the filter list.
+    pub rules: Vec<Rule>,
+    /// The filters that could not be converted.
+    pub unconverted: Vec<UnconvertedFilter>,
+}
+
+impl FilterListConversion {
+    /// Serializes the rules as a static ruleset file.
+    pub fn to_json(&self) -> Result<String, RuleError> {
+        ruleset_json(&self.rules)
+    }
+}
+
+/// A filter that [`convert_filter_list`] could not convert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconvertedFilter {
+    /// The 1-based line number in the filter list.
+    pub line: usize,
+    pub filter: String,
+    pub reason: UnconvertedReason,
+}
+
+/// Why a filter could not be converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnconvertedReason {
+    /// Element hiding and other cosmetic filters have no network rule equivalent.
+    Cosmetic,
+    /// The filter uses an option that has no rule equivalent.
+    UnsupportedOption(String),
+    /// An option has an invalid value.
+    InvalidOption(String),
+    /// The regular expression of the filter does not compile.
+    InvalidRegex(String),
+    /// The converted rule would be rejected by the browser.
+    InvalidRule(RuleError),
+    /// [`FilterListOptions::max_rules`] was reached.
+    RuleLimitExceeded,
+    /// [`FilterListOptions::max_regex_rules`] was reached.
+    RegexRuleLimitExceeded,
+}
+
+impl fmt::Display for UnconvertedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cosmetic => f.write_str("cosmetic filters are not supported"),
+            Self::UnsupportedOption(option) => write!(f, "unsupported option {option:?}"),
+            Self::InvalidOption(option) => write!(f, "invalid option {option:?}"),
+            Self::InvalidRegex(error) => write!(f, "invalid regular expression: {error}"),
+            Self::InvalidRule(error) => error.fmt(f),
+            Self::RuleLimitExceeded => f.write_str("the rule limit is exceeded"),
+            Self::RegexRuleLimitExceeded => f.write_str("the regex rule limit is exceeded"),
+        }
+    }
+}
+
+impl fmt::Display for UnconvertedFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.filter, self.reason)
+    }
+}
+
+/// Converts the network filters of an Adblock Plus filter list into rules.
+///
+/// Supported are URL patterns (`||`, `|`, `*` and `^`), `/regex/` patterns,
+/// `@@` exceptions and the options `third-party`, `first-party`, `domain`,
+/// `match-case`, `important`, `method`, `all`, `document` and the resource
+/// types. Comments and empty lines are skipped; every other filter that
+/// can't be converted is reported in [`FilterListConversion::unconverted`].
+///
+/// Requires the `rule-engine` feature.
+pub fn convert_filter_list(list: &str, options: &FilterListOptions) -> FilterListConversion {
+    let mut conversion = FilterListConversion::default();
+    let mut next_id = options.first_rule_id;
+    let mut regex_rules = 0;
+    for (index, line) in list.lines().enumerate() {
+        let filter = line.trim();
+        if filter.is_empty() || filter.starts_with('!') || filter.starts_with('[') {
+            continue;
+        }
+        let mut unconverted = |reason| {
+            conversion.unconverted.push(UnconvertedFilter {
+                line: index + 1,
+                filter: filter.to_owned(),
+                reason,
+            })
+        };
+        let rule = match convert_filter(filter, next_id) {
+            Ok(rule) => rule,
+            Err(reason) => {
+                unconverted(reason);
+                continue;
+            }
+        };
+        let is_regex = rule.condition.regex_filter.is_some();
+        if conversion.rules.len() == options.max_rules {
+            unconverted(UnconvertedReason::RuleLimitExceeded);
+        } else if is_regex && regex_rules == options.max_regex_rules {
+            unconverted(UnconvertedReason::RegexRuleLimitExceeded);
+        } else if let Err(error) = rule.validate() {
+            unconverted(UnconvertedReason::InvalidRule(error));
+        } else {
+            regex_rules += usize::from(is_regex);
+            next_id += 1;
+            conversion.rules.push(rule);
+        }
+    }
+    conversion
+}
+
+fn is_cosmetic(filter: &str) -> bool {
+    ["##", "#@#", "#?#", "#$#", "#%#", "#@?#", "#@$#"]
+        .iter()
+        .any(|separator| filter.contains(separator))
+}
+
+/// Splits a filter into its pattern and options.
+fn split_options(filter: &str) -> (&str, Option<&str>) {
+    // The `$` of a regex pattern is not an option separator.
+    let search_from = filter
+        .strip_prefix('/')
+        .and_then(|rest| rest.rfind('/'))
+        .map_or(0, |index| index + 1);
+    match filter[search_from..].rfind('$') {
+        Some(index) => {
+            let index = search_from + index;
+            (&filter[..index], Some(&filter[index + 1..]))
+        }
+        None => (filter, None),
+    }
+}
+
+fn resource_type(option: &str) -> Option<ResourceType> {
+    Some(match option {
+        "script" => ResourceType::Script,
+        "image" => ResourceType::Image,
+        "stylesheet" | "css" => ResourceType::Stylesheet,
+        "object" => ResourceType::Object,
+        "xmlhttprequest" | "xhr" => ResourceType::XmlHttpRequest,
+        "subdocument" | "frame" => ResourceType::SubFrame,
+        "ping" | "beacon" => ResourceType::Ping,
+        "media" => ResourceType::Media,
+        "font" => ResourceType::Font,
+        "websocket" => ResourceType::WebSocket,
+        "webtransport" => ResourceType::WebTransport,
+        "webbundle" => ResourceType::WebBundle,
+        "csp_report" => ResourceType::CspReport,
+        "other" => ResourceType::Other,
+        _ => return None,
+    })
+}
+
+const ALL_RESOURCE_TYPES: [ResourceType; 15] = [
+    ResourceType::MainFrame,
+    ResourceType::SubFrame,
+    ResourceType::Stylesheet,
+    ResourceType::Script,
+    ResourceType::Image,
+    ResourceType::Font,
+    ResourceType::Object,
+    ResourceType::XmlHttpRequest,
+    ResourceType::Ping,
+    ResourceType::CspReport,
+    ResourceType::Media,
+    ResourceType::WebSocket,
+    ResourceType::WebTransport,
+    ResourceType::WebBundle,
+    ResourceType::Other,
+];
+
+fn convert_filter(filter: &str, id: u32) -> Result<Rule, UnconvertedReason> {
+    if is_cosmetic(filter) {
+        return Err(UnconvertedReason::Cosmetic);
+    }
+    let (is_exception, filter) = match filter.strip_prefix("@@") {
+        Some(filter) => (true, filter),
+        None => (false, filter),
+    };
+    let (pattern, options) = split_options(filter);
+    let mut condition = RuleCondition::default();
+    let mut types = Vec::new();
+    let mut excluded_types = Vec::new();
+    let mut important = false;
+    let mut document = false;
+    for option in options.into_iter().flat_map(|options| options.split(',')) {
+        let option = option.trim();
+        let (negated, name) = match option.strip_prefix('~') {
+            Some(name) => (true, name),
+            None => (false, option),
+        };
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (name, None),
+        };
+        match (name, value) {
+            ("third-party" | "3p", None) => {
+                condition.domain_type = Some(if negated {
+                    DomainType::FirstParty
+                } else {
+                    DomainType::ThirdParty
+                });
+            }
+            ("first-party" | "1p", None) => {
+                condition.domain_type = Some(if negated {
+                    DomainType::ThirdParty
+                } else {
+                    DomainType::FirstParty
+                });
+            }
+            ("match-case", None) if !negated => condition.is_url_filter_case_sensitive = Some(true),
+            ("important", None) if !negated => important = true,
+            ("document" | "doc", None) if !negated => document = true,
+            ("all", None) if !negated => types.extend(ALL_RESOURCE_TYPES.iter().cloned()),
+            ("domain" | "from", Some(domains)) if !negated => {
+                let (included, excluded) = split_list(domains, option, |domain| {
+                    let domain = domain.to_ascii_lowercase();
+                    domain.is_ascii().then_some(domain)
+                })?;
+                condition.initiator_domains = (!included.is_empty()).then_some(included);
+                condition.excluded_initiator_domains = (!excluded.is_empty()).then_some(excluded);
+            }
+            ("method", Some(methods)) if !negated => {
+                let (included, excluded) = split_list(methods, option, |method| {
+                    match RequestMethod::from(method.to_ascii_lowercase()) {
+                        RequestMethod::Unknown(_) => None,
+                        method => Some(method),
+                    }
+                })?;
+                condition.request_methods = (!included.is_empty()).then_some(included);
+                condition.excluded_request_methods = (!excluded.is_empty()).then_some(excluded);
+            }
+            (name, None) => match resource_type(name) {
+                Some(type_) if negated => excluded_types.push(type_),
+                Some(type_) => types.push(type_),
+                None => return Err(UnconvertedReason::UnsupportedOption(option.to_owned())),
+            },
+            _ => return Err(UnconvertedReason::UnsupportedOption(option.to_owned())),
+        }
+    }
+
+    let action = if document {
+        if !is_exception || !types.is_empty() {
+            return Err(UnconvertedReason::UnsupportedOption("document".to_owned()));
+        }
+        types.extend([ResourceType::MainFrame, ResourceType::SubFrame]);
+        RuleAction::allow_all_requests()
+    } else if is_exception {
+        RuleAction::allow()
+    } else {
+        RuleAction::block()
+    };
+    if !types.is_empty() {
+        let mut unique = Vec::with_capacity(types.len());
+        for type_ in types {
+            if !unique.contains(&type_) {
+                unique.push(type_);
+            }
+        }
+        condition.resource_types = Some(unique);
+    } else if !excluded_types.is_empty() {
+        condition.excluded_resource_types = Some(excluded_types);
+    }
+
+    if let Some(regex) = pattern
+        .strip_prefix('/')
+        .and_then(|pattern| pattern.strip_suffix('/'))
+        .filter(|regex| !regex.is_empty())
+    {
+        regex::Regex::new(regex)
+            .map_err(|error| UnconvertedReason::InvalidRegex(error.to_string()))?;
+        condition.regex_filter = Some(regex.to_owned());
+    } else {
+        // `||*` is rejected by the browser, and means the same as `*`.
+        let pattern = match pattern.strip_prefix("||*") {
+            Some(rest) => format!("*{rest}"),
+            None => pattern.to_owned(),
+        };
+        if !pattern.is_empty() && pattern != "*" {
+            condition.url_filter = Some(pattern);
+        }
+    }
+
+    let priority = match (is_exception, important) {
+        (false, true) => IMPORTANT_PRIORITY,
+        (true, _) => EXCEPTION_PRIORITY,
+        (false, false) => BLOCK_PRIORITY,
+    };
+    Ok(Rule::new(id, action, condition).with_priority(priority))
+}
+
+/// Splits a `|` separated option value into its included and `~` excluded entries.
+fn split_list<T>(
+    value: &str,
+    option: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<(Vec<T>, Vec<T>), UnconvertedReason> {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    for entry in value.split('|') {
+        let (list, entry) = match entry.strip_prefix('~') {
+            Some(entry) => (&mut excluded, entry),
+            None => (&mut included, entry),
+        };
+        match parse(entry).filter(|_| !entry.is_empty()) {
+            Some(entry) => list.push(entry),
+            None => return Err(UnconvertedReason::InvalidOption(option.to_owned())),
+        }
+    }
+    Ok((included, excluded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: RuleAction, priority: u32, update: impl FnOnce(&mut RuleCondition)) -> Rule {
+        let mut condition = RuleCondition::default();
+        update(&mut condition);
+        Rule::new(1, action, condition).with_priority(priority)
+    }
+
+    #[test]
+    fn convert_filter_table() {
+        let cases = [
+            (
+                "||ads.example.com^",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("||ads.example.com^".into())
+                }),
+            ),
+            (
+                "@@||example.com/ads.js",
+                rule(RuleAction::allow(), 2, |c| {
+                    c.url_filter = Some("||example.com/ads.js".into())
+                }),
+            ),
+            (
+                "||tracker.example^$important",
+                rule(RuleAction::block(), 3, |c| {
+                    c.url_filter = Some("||tracker.example^".into())
+                }),
+            ),
+            (
+                "@@||example.com^$important",
+                rule(RuleAction::allow(), 2, |c| {
+                    c.url_filter = Some("||example.com^".into())
+                }),
+            ),
+            (
+                "@@||example.com^$document",
+                rule(RuleAction::allow_all_requests(), 2, |c| {
+                    c.url_filter = Some("||example.com^".into());
+                    c.resource_types = Some(vec![ResourceType::MainFrame, ResourceType::SubFrame]);
+                }),
+            ),
+            (
+                "/banner$script,domain=a.com|~b.a.com",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("/banner".into());
+                    c.resource_types = Some(vec![ResourceType::Script]);
+                    c.initiator_domains = Some(vec!["a.com".into()]);
+                    c.excluded_initiator_domains = Some(vec!["b.a.com".into()]);
+                }),
+            ),
+            (
+                "||cdn.example^$~third-party",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("||cdn.example^".into());
+                    c.domain_type = Some(DomainType::FirstParty);
+                }),
+            ),
+            (
+                "||cdn.example^$third-party,~image,~script",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("||cdn.example^".into());
+                    c.domain_type = Some(DomainType::ThirdParty);
+                    c.excluded_resource_types =
+                        Some(vec![ResourceType::Image, ResourceType::Script]);
+                }),
+            ),
+            (
+                r"/^https?:\/\/ads\d+\.example$/$image",
+                rule(RuleAction::block(), 1, |c| {
+                    c.regex_filter = Some(r"^https?:\/\/ads\d+\.example$".into());
+                    c.resource_types = Some(vec![ResourceType::Image]);
+                }),
+            ),
+            (
+                "||*/ads/*$xhr,xmlhttprequest",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("*/ads/*".into());
+                    c.resource_types = Some(vec![ResourceType::XmlHttpRequest]);
+                }),
+            ),
+            (
+                "/pixel.gif$method=get|~post,match-case",
+                rule(RuleAction::block(), 1, |c| {
+                    c.url_filter = Some("/pixel.gif".into());
+                    c.is_url_filter_case_sensitive = Some(true);
+                    c.request_methods = Some(vec![RequestMethod::Get]);
+                    c.excluded_request_methods = Some(vec![RequestMethod::Post]);
+                }),
+            ),
+            (
+                "*$ping",
+                rule(RuleAction::block(), 1, |c| {
+                    c.resource_types = Some(vec![ResourceType::Ping])
+                }),
+            ),
+        ];
+        for (filter, expected) in cases {
+            assert_eq!(convert_filter(filter, 1), Ok(expected), "{filter}");
+        }
+    }
+
+    #[test]
+    fn unconvertible_filters() {
+        let cases = [
+            ("example.com##.ad", UnconvertedReason::Cosmetic),
+            ("example.com#@#.ad", UnconvertedReason::Cosmetic),
+            ("example.com#?#.ad:has(a)", UnconvertedReason::Cosmetic),
+            (
+                "||example.com^$csp=script-src 'none'",
+                UnconvertedReason::UnsupportedOption("csp=script-src 'none'".into()),
+            ),
+            (
+                "||example.com^$popup",
+                UnconvertedReason::UnsupportedOption("popup".into()),
+            ),
+            (
+                "||example.com^$document",
+                UnconvertedReason::UnsupportedOption("document".into()),
+            ),
+            (
+                "||example.com^$domain=a.com|",
+                UnconvertedReason::InvalidOption("domain=a.com|".into()),
+            ),
+            (
+                "||example.com^$method=fetch",
+                UnconvertedReason::InvalidOption("method=fetch".into()),
+            ),
+        ];
+        for (filter, expected) in cases {
+            assert_eq!(convert_filter(filter, 1), Err(expected), "{filter}");
+        }
+        assert!(matches!(
+            convert_filter("/ads(/", 1),
+            Err(UnconvertedReason::InvalidRegex(_))
+        ));
+    }
+
+    #[test]
+    fn convert_filter_list_skips_comments_and_numbers_rules() {
+        let list = "[Adblock Plus 2.0]\n! Title: Test\n\n||a.example^\nexample.com##.ad\n  @@||b.example^  \n||c.example^$csp=default-src 'self'\n";
+        let options = FilterListOptions {
+            first_rule_id: 10,
+            ..FilterListOptions::default()
+        };
+        let conversion = convert_filter_list(list, &options);
+        let ids: Vec<_> = conversion.rules.iter().map(|rule| rule.id).collect();
+        assert_eq!(ids, [10, 11]);
+        assert_eq!(
+            conversion.rules[1].condition.url_filter.as_deref(),
+            Some("||b.example^")
+        );
+        let unconverted: Vec<_> = conversion
+            .unconverted
+            .iter()
+            .map(|filter| (filter.line, filter.reason.clone()))
+            .collect();
+        assert_eq!(
+            unconverted,
+            [
+                (5, UnconvertedReason::Cosmetic),
+                (
+                    7,
+                    UnconvertedReason::UnsupportedOption("csp=default-src 'self'".into())
+                ),
+            ]
+        );
+        assert!(conversion.to_json().is_ok());
+    }
+
+    #[test]
+    fn convert_filter_list_limits() {
+        let list = "/a+/\n||a.example^\n/b+/\n||b.example^\n||c.example^\n";
+        let options = FilterListOptions {
+            first_rule_id: 1,
+            max_rules: 3,
+            max_regex_rules: 1,
+        };
+        let conversion = convert_filter_list(list, &options);
+        let rules: Vec<_> = conversion
+            .rules
+            .iter()
+            .map(|rule| {
+                let condition = &rule.condition;
+                let filter = condition
+                    .regex_filter
+                    .as_ref()
+                    .or(condition.url_filter.as_ref());
+                (rule.id, filter.unwrap().as_str())
+            })
+            .collect();
+        assert_eq!(rules, [(1, "a+"), (2, "||a.example^"), (3, "||b.example^")]);
+        let unconverted: Vec<_> = conversion
+            .unconverted
+            .iter()
+            .map(|filter| (filter.line, filter.reason.clone()))
+            .collect();
+        assert_eq!(
+            unconverted,
+            [
+                (3, UnconvertedReason::RegexRuleLimitExceeded),
+                (5, UnconvertedReason::RuleLimitExceeded),
+            ]
+        );
+    }
+}
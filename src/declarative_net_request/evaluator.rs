@@ -0,0 +1,641 @@
+//! Offline evaluation of `declarativeNetRequest` rules.
+
+use super::{
+    validate_rules, DomainType, MatchedRule, RequestMethod, Rule, RuleAction, RuleActionType,
+    RuleError, RuleErrorKind, TestMatchRequestDetails, DYNAMIC_RULESET_ID, SESSION_RULESET_ID,
+};
+use crate::{ResourceType, TabId};
+use regex::{Regex, RegexBuilder};
+use std::cmp::Reverse;
+
+/// Evaluates rules against simulated requests, without a browser.
+///
+/// The engine follows the matching algorithm of Chromium: the matching rule
+/// with the highest priority wins, and ties are broken by the action type in
+/// the order allow, allowAllRequests, block, upgradeScheme, redirect.
+/// `modifyHeaders` rules with a higher priority than the winning allow or
+/// allowAllRequests rule are reported separately.
+///
+/// Some details are approximated: whether a request is third party is
+/// decided by the last two labels of the host names rather than the public
+/// suffix list, and `regex_filter`s use the syntax of the `regex` crate,
+/// which is close to, but not the same as, RE2.
+///
+/// Requires the `rule-engine` feature.
+#[derive(Debug, Clone, Default)]
+pub struct RuleEngine {
+    rules: Vec<CompiledRule>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    ruleset_id: String,
+    rule: Rule,
+    pattern: Option<Regex>,
+}
+
+/// A rule that matched a request, with the action it takes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    pub rule: MatchedRule,
+    /// The effective priority of the rule.
+    pub priority: u32,
+    pub action: RuleAction,
+}
+
+/// The result of [`RuleEngine::evaluate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchOutcome {
+    /// The rule that decides what happens to the request, if any.
+    ///
+    /// For a request within a frame whose navigation matched an
+    /// `allowAllRequests` rule, this is that rule.
+    pub action: Option<RuleMatch>,
+    /// The `modifyHeaders` rules applied to the request, highest priority first.
+    pub modify_headers: Vec<RuleMatch>,
+}
+
+impl MatchOutcome {
+    /// Whether the request is blocked.
+    pub fn is_blocked(&self) -> bool {
+        self.action_type() == Some(&RuleActionType::Block)
+    }
+
+    /// Whether the request is explicitly allowed.
+    pub fn is_allowed(&self) -> bool {
+        matches!(
+            self.action_type(),
+            Some(RuleActionType::Allow | RuleActionType::AllowAllRequests)
+        )
+    }
+
+    /// The action type of the winning rule.
+    pub fn action_type(&self) -> Option<&RuleActionType> {
+        self.action.as_ref().map(|matched| &matched.action.type_)
+    }
+
+    /// The matched rules in the form of
+    /// [`DeclarativeNetRequest::test_match_outcome`](crate::DeclarativeNetRequest::test_match_outcome).
+    pub fn matched_rules(&self) -> Vec<MatchedRule> {
+        self.action
+            .iter()
+            .chain(&self.modify_headers)
+            .map(|matched| matched.rule.clone())
+            .collect()
+    }
+}
+
+impl RuleEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rules of a static ruleset, or of [`DYNAMIC_RULESET_ID`] or
+    /// [`SESSION_RULESET_ID`].
+    ///
+    /// The rules are validated like [`validate_rules`] does, and their filters are compiled.
+    pub fn add_ruleset(
+        &mut self,
+        ruleset_id: impl Into<String>,
+        rules: &[Rule],
+    ) -> Result<&mut Self, RuleError> {
+        validate_rules(rules)?;
+        let ruleset_id = ruleset_id.into();
+        let compiled = rules
+            .iter()
+            .map(|rule| {
+                Ok(CompiledRule {
+                    ruleset_id: ruleset_id.clone(),
+                    pattern: compile_pattern(rule)?,
+                    rule: rule.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, RuleError>>()?;
+        self.rules.extend(compiled);
+        Ok(self)
+    }
+
+    /// Adds dynamic rules.
+    pub fn add_dynamic_rules(&mut self, rules: &[Rule]) -> Result<&mut Self, RuleError> {
+        self.add_ruleset(DYNAMIC_RULESET_ID, rules)
+    }
+
+    /// Adds session rules.
+    pub fn add_session_rules(&mut self, rules: &[Rule]) -> Result<&mut Self, RuleError> {
+        self.add_ruleset(SESSION_RULESET_ID, rules)
+    }
+
+    /// Evaluates a request that isn't made from within a frame, e.g. a
+    /// `main_frame` navigation.
+    pub fn evaluate(&self, request: &TestMatchRequestDetails) -> MatchOutcome {
+        self.evaluate_with(request, None)
+    }
+
+    /// Evaluates a request made from within a frame hierarchy.
+    ///
+    /// `frames` are the `main_frame` and `sub_frame` requests that loaded
+    /// the documents of the hierarchy, outermost first. If one of them is
+    /// allowed by an `allowAllRequests` rule, the request is allowed with
+    /// that rule's priority.
+    pub fn evaluate_in_frames(
+        &self,
+        request: &TestMatchRequestDetails,
+        frames: &[TestMatchRequestDetails],
+    ) -> MatchOutcome {
+        let mut inherited = None;
+        for frame in frames {
+            let outcome = self.evaluate_with(frame, inherited.clone());
+            if let Some(matched) = outcome.action {
+                if matched.action.type_ == RuleActionType::AllowAllRequests {
+                    inherited = Some(matched);
+                }
+            }
+        }
+        self.evaluate_with(request, inherited)
+    }
+
+    fn evaluate_with(
+        &self,
+        request: &TestMatchRequestDetails,
+        inherited: Option<RuleMatch>,
+    ) -> MatchOutcome {
+        let request = Request::new(request);
+        let mut action: Option<RuleMatch> = inherited;
+        let mut modify_headers = Vec::new();
+        for compiled in &self.rules {
+            let type_ = &compiled.rule.action.type_;
+            let is_modify_headers = *type_ == RuleActionType::ModifyHeaders;
+            if !is_modify_headers && action_rank(type_).is_none() || !compiled.matches(&request) {
+                continue;
+            }
+            let matched = compiled.to_match();
+            if is_modify_headers {
+                modify_headers.push(matched);
+            } else if action
+                .as_ref()
+                .is_none_or(|current| outranks(&matched, current))
+            {
+                action = Some(matched);
+            }
+        }
+        // Headers are only modified if the request is sent unchanged, and not
+        // by rules that an allow rule of a higher priority overrides.
+        match &action {
+            Some(matched) if is_allow(&matched.action.type_) => {
+                modify_headers.retain(|header: &RuleMatch| header.priority > matched.priority)
+            }
+            Some(_) => modify_headers.clear(),
+            None => {}
+        }
+        modify_headers.sort_by_key(|header| Reverse(header.priority));
+        MatchOutcome {
+            action,
+            modify_headers,
+        }
+    }
+}
+
+fn is_allow(type_: &RuleActionType) -> bool {
+    matches!(
+        type_,
+        RuleActionType::Allow | RuleActionType::AllowAllRequests
+    )
+}
+
+/// Whether `a` takes precedence over `b`.
+fn outranks(a: &RuleMatch, b: &RuleMatch) -> bool {
+    let key = |matched: &RuleMatch| {
+        let rank = action_rank(&matched.action.type_).unwrap_or(u8::MAX);
+        (matched.priority, Reverse(rank))
+    };
+    key(a) > key(b)
+}
+
+/// The tie-break order of the actions that decide the fate of a request.
+fn action_rank(type_: &RuleActionType) -> Option<u8> {
+    match type_ {
+        RuleActionType::Allow => Some(0),
+        RuleActionType::AllowAllRequests => Some(1),
+        RuleActionType::Block => Some(2),
+        RuleActionType::UpgradeScheme => Some(3),
+        RuleActionType::Redirect => Some(4),
+        RuleActionType::ModifyHeaders | RuleActionType::Unknown(_) => None,
+    }
+}
+
+fn compile_pattern(rule: &Rule) -> Result<Option<Regex>, RuleError> {
+    let condition = &rule.condition;
+    let pattern = match (&condition.url_filter, &condition.regex_filter) {
+        (Some(filter), _) => url_filter_to_regex(filter),
+        (None, Some(regex)) => regex.clone(),
+        (None, None) => return Ok(None),
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!condition.is_url_filter_case_sensitive.unwrap_or(false))
+        .build()
+        .map(Some)
+        .map_err(|error| RuleError {
+            rule_id: rule.id,
+            kind: RuleErrorKind::InvalidRegexFilter(error.to_string()),
+        })
+}
+
+/// Translates the `url_filter` syntax into a regular expression.
+fn url_filter_to_regex(filter: &str) -> String {
+    let mut pattern = String::new();
+    let mut rest = filter;
+    if let Some(stripped) = rest.strip_prefix("||") {
+        // The scheme, credentials and any subdomains of the host.
+        pattern.push_str(r"^[^:/?#]+:(?://)?(?:[^/?#@]*@)?(?:[^/?#:@]*\.)?");
+        rest = stripped;
+    } else if let Some(stripped) = rest.strip_prefix('|') {
+        pattern.push('^');
+        rest = stripped;
+    }
+    let anchored_end = rest.ends_with('|');
+    let rest = rest.strip_suffix('|').unwrap_or(rest);
+    for c in rest.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '^' => pattern.push_str(r"(?:[^a-zA-Z0-9_\-.%]|$)"),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    if anchored_end {
+        pattern.push('$');
+    }
+    pattern
+}
+
+struct Request<'a> {
+    url: &'a str,
+    host: String,
+    initiator_host: Option<String>,
+    method: RequestMethod,
+    type_: &'a ResourceType,
+    tab_id: i32,
+}
+
+impl<'a> Request<'a> {
+    fn new(details: &'a TestMatchRequestDetails) -> Self {
+        Self {
+            url: &details.url,
+            host: host(&details.url),
+            initiator_host: details.initiator.as_deref().map(host),
+            method: details.method.clone().unwrap_or(RequestMethod::Get),
+            type_: &details.type_,
+            tab_id: details.tab_id.map_or(-1, |id| id.0),
+        }
+    }
+
+    fn is_third_party(&self) -> bool {
+        self.initiator_host
+            .as_ref()
+            .is_some_and(|initiator| site(initiator) != site(&self.host))
+    }
+}
+
+impl CompiledRule {
+    fn to_match(&self) -> RuleMatch {
+        RuleMatch {
+            rule: MatchedRule {
+                rule_id: self.rule.id,
+                ruleset_id: self.ruleset_id.clone(),
+                extension_id: None,
+            },
+            priority: self.rule.priority.unwrap_or(1),
+            action: self.rule.action.clone(),
+        }
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        let condition = &self.rule.condition;
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(request.url) {
+                return false;
+            }
+        }
+        if !matches_domains(
+            Some(&request.host),
+            &condition.request_domains,
+            &condition.excluded_request_domains,
+        ) {
+            return false;
+        }
+        if !matches_domains(
+            request.initiator_host.as_ref(),
+            &condition.initiator_domains,
+            &condition.excluded_initiator_domains,
+        ) {
+            return false;
+        }
+        if let Some(domain_type) = &condition.domain_type {
+            let third_party = request.is_third_party();
+            match domain_type {
+                DomainType::FirstParty if third_party => return false,
+                DomainType::ThirdParty if !third_party => return false,
+                DomainType::Unknown(_) => return false,
+                _ => {}
+            }
+        }
+        let type_matches = match &condition.resource_types {
+            Some(types) => types.contains(request.type_),
+            None => {
+                condition.excluded_resource_types.is_some()
+                    || *request.type_ != ResourceType::MainFrame
+            }
+        };
+        if !type_matches || excluded(&condition.excluded_resource_types, request.type_) {
+            return false;
+        }
+        if !included(&condition.request_methods, &request.method)
+            || excluded(&condition.excluded_request_methods, &request.method)
+        {
+            return false;
+        }
+        let tab_id = TabId(request.tab_id);
+        included(&condition.tab_ids, &tab_id) && !excluded(&condition.excluded_tab_ids, &tab_id)
+    }
+}
+
+fn included<T: PartialEq>(list: &Option<Vec<T>>, value: &T) -> bool {
+    list.as_ref().is_none_or(|list| list.contains(value))
+}
+
+fn excluded<T: PartialEq>(list: &Option<Vec<T>>, value: &T) -> bool {
+    list.as_ref().is_some_and(|list| list.contains(value))
+}
+
+fn matches_domains(
+    host: Option<&String>,
+    domains: &Option<Vec<String>>,
+    excluded_domains: &Option<Vec<String>>,
+) -> bool {
+    let Some(host) = host else {
+        // Requests without an initiator never match an initiator domain.
+        return domains.is_none();
+    };
+    let is_in = |list: &Vec<String>| list.iter().any(|domain| is_subdomain(host, domain));
+    domains.as_ref().is_none_or(is_in) && !excluded_domains.as_ref().is_some_and(is_in)
+}
+
+fn is_subdomain(host: &str, domain: &str) -> bool {
+    host.strip_suffix(domain)
+        .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with('.'))
+}
+
+/// The lowercase host of a URL or origin, without credentials and port.
+fn host(url: &str) -> String {
+    let authority = url.split_once("://").map_or("", |(_, rest)| rest);
+    let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = if host.starts_with('[') {
+        host.split_inclusive(']').next().unwrap_or(host)
+    } else {
+        host.split(':').next().unwrap_or(host)
+    };
+    host.to_ascii_lowercase()
+}
+
+/// An approximation of the registrable domain: the last two labels.
+fn site(host: &str) -> &str {
+    if host.starts_with('[') || host.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return host;
+    }
+    match host.rmatch_indices('.').nth(1) {
+        Some((index, _)) => &host[index + 1..],
+        None => host,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModifyHeaderInfo, Redirect, RuleCondition};
+
+    fn rule(id: u32, priority: u32, action: RuleAction, url_filter: &str) -> Rule {
+        let condition = RuleCondition {
+            url_filter: Some(url_filter.to_owned()),
+            ..RuleCondition::default()
+        };
+        Rule::new(id, action, condition).with_priority(priority)
+    }
+
+    fn frame_rule(id: u32, priority: u32, action: RuleAction, url_filter: &str) -> Rule {
+        let mut rule = rule(id, priority, action, url_filter);
+        rule.condition.resource_types = Some(vec![ResourceType::MainFrame, ResourceType::SubFrame]);
+        rule
+    }
+
+    fn headers() -> RuleAction {
+        RuleAction::modify_headers(vec![ModifyHeaderInfo::remove("cookie")], vec![])
+    }
+
+    fn engine(rules: &[Rule]) -> RuleEngine {
+        let mut engine = RuleEngine::new();
+        engine.add_dynamic_rules(rules).unwrap();
+        engine
+    }
+
+    fn script(url: &str) -> TestMatchRequestDetails {
+        TestMatchRequestDetails::new(url, ResourceType::Script)
+    }
+
+    fn winner(outcome: &MatchOutcome) -> Option<u32> {
+        outcome.action.as_ref().map(|matched| matched.rule.rule_id)
+    }
+
+    fn header_ids(outcome: &MatchOutcome) -> Vec<u32> {
+        outcome
+            .modify_headers
+            .iter()
+            .map(|matched| matched.rule.rule_id)
+            .collect()
+    }
+
+    #[test]
+    fn higher_priority_wins() {
+        let engine = engine(&[
+            rule(1, 1, RuleAction::allow(), "example"),
+            rule(2, 2, RuleAction::block(), "example"),
+        ]);
+        let outcome = engine.evaluate(&script("https://example.com/a.js"));
+        assert_eq!(winner(&outcome), Some(2));
+        assert!(outcome.is_blocked());
+    }
+
+    #[test]
+    fn ties_are_broken_by_action_type() {
+        let redirect = RuleAction::redirect(Redirect::url("https://example.org"));
+        let order = [
+            (1, RuleAction::allow()),
+            (2, RuleAction::allow_all_requests()),
+            (3, RuleAction::block()),
+            (4, RuleAction::upgrade_scheme()),
+            (5, redirect),
+        ];
+        // Each action wins against all actions after it in the order.
+        for start in 0..order.len() {
+            let rules: Vec<_> = order[start..]
+                .iter()
+                .rev()
+                .map(|(id, action)| frame_rule(*id, 1, action.clone(), "example"))
+                .collect();
+            let request =
+                TestMatchRequestDetails::new("http://example.com", ResourceType::SubFrame);
+            assert_eq!(
+                winner(&engine(&rules).evaluate(&request)),
+                Some(order[start].0)
+            );
+        }
+    }
+
+    #[test]
+    fn allow_all_requests_is_inherited_by_the_frame_hierarchy() {
+        let engine = engine(&[
+            frame_rule(1, 2, RuleAction::allow_all_requests(), "||example.com^"),
+            rule(2, 1, RuleAction::block(), "ads"),
+            rule(3, 3, RuleAction::block(), "tracker"),
+        ]);
+        let frames = [TestMatchRequestDetails::new(
+            "https://example.com/",
+            ResourceType::MainFrame,
+        )];
+
+        let outcome = engine.evaluate_in_frames(&script("https://cdn.test/ads.js"), &frames);
+        assert_eq!(winner(&outcome), Some(1));
+        assert!(outcome.is_allowed());
+
+        // A block rule of a higher priority still wins.
+        let outcome = engine.evaluate_in_frames(&script("https://cdn.test/tracker.js"), &frames);
+        assert_eq!(winner(&outcome), Some(3));
+
+        // Outside of the frame hierarchy the block rule applies.
+        let outcome = engine.evaluate(&script("https://cdn.test/ads.js"));
+        assert_eq!(winner(&outcome), Some(2));
+    }
+
+    #[test]
+    fn modify_headers_are_pruned_by_allow_priority() {
+        let engine = engine(&[
+            rule(1, 2, RuleAction::allow(), "example"),
+            rule(2, 1, headers(), "example"),
+            rule(3, 3, headers(), "example"),
+            rule(4, 4, headers(), "example"),
+        ]);
+        let outcome = engine.evaluate(&script("https://example.com/a.js"));
+        assert_eq!(winner(&outcome), Some(1));
+        assert_eq!(header_ids(&outcome), [4, 3]);
+        assert_eq!(
+            outcome
+                .matched_rules()
+                .iter()
+                .map(|rule| rule.rule_id)
+                .collect::<Vec<_>>(),
+            [1, 4, 3]
+        );
+    }
+
+    #[test]
+    fn modify_headers_are_dropped_when_blocked() {
+        let engine = engine(&[
+            rule(1, 1, RuleAction::block(), "example"),
+            rule(2, 5, headers(), "example"),
+        ]);
+        let outcome = engine.evaluate(&script("https://example.com/a.js"));
+        assert!(outcome.is_blocked());
+        assert!(outcome.modify_headers.is_empty());
+
+        let engine = self::engine(&[rule(2, 5, headers(), "example")]);
+        let outcome = engine.evaluate(&script("https://example.com/a.js"));
+        assert_eq!(outcome.action, None);
+        assert_eq!(header_ids(&outcome), [2]);
+    }
+
+    #[test]
+    fn main_frame_is_excluded_by_default() {
+        let engine = engine(&[rule(1, 1, RuleAction::block(), "example")]);
+        let main_frame =
+            TestMatchRequestDetails::new("https://example.com/", ResourceType::MainFrame);
+        let sub_frame =
+            TestMatchRequestDetails::new("https://example.com/", ResourceType::SubFrame);
+        assert_eq!(winner(&engine.evaluate(&main_frame)), None);
+        assert_eq!(winner(&engine.evaluate(&sub_frame)), Some(1));
+
+        let mut excluding_scripts = rule(1, 1, RuleAction::block(), "example");
+        excluding_scripts.condition.excluded_resource_types = Some(vec![ResourceType::Script]);
+        let engine = self::engine(&[excluding_scripts]);
+        assert_eq!(winner(&engine.evaluate(&main_frame)), Some(1));
+        assert_eq!(
+            winner(&engine.evaluate(&script("https://example.com/"))),
+            None
+        );
+    }
+
+    #[test]
+    fn url_filter_syntax() {
+        let cases = [
+            ("||example.com^", "https://example.com/a", true),
+            ("||example.com^", "https://sub.example.com:8080/a", true),
+            ("||example.com^", "https://user@example.com/", true),
+            ("||example.com^", "https://example.com", true),
+            ("||example.com^", "https://example.community/", false),
+            ("||example.com^", "https://notexample.com/", false),
+            ("||example.com^", "https://other.org/?u=example.com", false),
+            ("|https://a.com/", "https://a.com/x", true),
+            ("|https://a.com/", "http://b.com/?https://a.com/", false),
+            (".js|", "https://a.com/x.js", true),
+            (".js|", "https://a.com/x.js?v=1", false),
+            ("ads*banner", "https://a.com/ads/big-banner.png", true),
+            ("ads^", "https://a.com/ads?x", true),
+            ("ads^", "https://a.com/ads-x", false),
+            ("a.b", "https://a.com/aXb", false),
+            ("EXAMPLE", "https://example.com/", true),
+        ];
+        for (filter, url, expected) in cases {
+            let regex = Regex::new(&format!("(?i){}", url_filter_to_regex(filter))).unwrap();
+            assert_eq!(regex.is_match(url), expected, "{filter} on {url}");
+        }
+    }
+
+    #[test]
+    fn case_sensitive_url_filter() {
+        let mut rule = rule(1, 1, RuleAction::block(), "EXAMPLE");
+        rule.condition.is_url_filter_case_sensitive = Some(true);
+        let engine = engine(&[rule]);
+        assert_eq!(
+            winner(&engine.evaluate(&script("https://example.com/"))),
+            None
+        );
+        assert_eq!(
+            winner(&engine.evaluate(&script("https://EXAMPLE.com/"))),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn domain_conditions() {
+        let mut third_party = rule(1, 1, RuleAction::block(), "tracker");
+        third_party.condition.domain_type = Some(DomainType::ThirdParty);
+        third_party.condition.excluded_initiator_domains = Some(vec!["trusted.com".into()]);
+        let engine = engine(&[third_party]);
+        let request = |initiator: &str| TestMatchRequestDetails {
+            initiator: Some(initiator.to_owned()),
+            ..script("https://cdn.tracker.net/t.js")
+        };
+        assert_eq!(
+            winner(&engine.evaluate(&request("https://news.org"))),
+            Some(1)
+        );
+        assert_eq!(
+            winner(&engine.evaluate(&request("https://www.tracker.net"))),
+            None
+        );
+        assert_eq!(
+            winner(&engine.evaluate(&request("https://a.trusted.com"))),
+            None
+        );
+    }
+}
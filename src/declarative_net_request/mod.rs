@@ -0,0 +1,370 @@
+//! Bindings to the `declarativeNetRequest` API.
+
+// Also built for the unit tests, which get `regex` as a dev-dependency.
+#[cfg(any(feature = "rule-engine", test))]
+mod adblock;
+#[cfg(any(feature = "rule-engine", test))]
+mod evaluator;
+mod rule;
+
+#[cfg(any(feature = "rule-engine", test))]
+pub use adblock::*;
+#[cfg(any(feature = "rule-engine", test))]
+pub use evaluator::*;
+pub use rule::*;
+
+use crate::{
+    json::{from_js, to_js},
+    DocumentLifecycle, EventTarget, FrameType, ResourceType, TabId,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// The number of static rules an extension is guaranteed to be able to enable.
+pub const GUARANTEED_MINIMUM_STATIC_RULES: usize = 30_000;
+
+/// The maximum number of dynamic and session rules, combined.
+pub const MAX_NUMBER_OF_DYNAMIC_RULES: usize = 30_000;
+
+/// The maximum number of dynamic and session rules that are not safe, i.e.
+/// that don't block, allow, allow all requests or upgrade the scheme.
+pub const MAX_NUMBER_OF_UNSAFE_DYNAMIC_RULES: usize = 5_000;
+
+/// The maximum number of regular expression rules of each ruleset and of the
+/// dynamic and session rules.
+pub const MAX_NUMBER_OF_REGEX_RULES: usize = 1_000;
+
+/// The maximum number of static rulesets declared in the manifest.
+pub const MAX_NUMBER_OF_STATIC_RULESETS: usize = 100;
+
+/// The maximum number of static rulesets that can be enabled at once.
+pub const MAX_NUMBER_OF_ENABLED_STATIC_RULESETS: usize = 50;
+
+/// The ruleset id of the dynamic rules in [`MatchedRule::ruleset_id`].
+pub const DYNAMIC_RULESET_ID: &str = "_dynamic";
+
+/// The ruleset id of the session rules in [`MatchedRule::ruleset_id`].
+pub const SESSION_RULESET_ID: &str = "_session";
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-MatchedRule
+/// A rule that matched a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedRule {
+    pub rule_id: u32,
+    /// The id of the static ruleset, or [`DYNAMIC_RULESET_ID`] or [`SESSION_RULESET_ID`].
+    pub ruleset_id: String,
+    /// The id of the extension that owns the rule, if it is another extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension_id: Option<String>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-MatchedRuleInfo
+/// A rule that matched a request in a tab.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedRuleInfo {
+    pub rule: MatchedRule,
+    /// The tab the request originated from, or -1 if it isn't related to a tab.
+    pub tab_id: TabId,
+    /// The time the rule was matched, in milliseconds since the epoch.
+    pub time_stamp: f64,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-MatchedRulesFilter
+/// Selects the rules returned by [`DeclarativeNetRequest::get_matched_rules`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MatchedRulesFilter {
+    /// Only rules matched in this tab.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab_id: Option<TabId>,
+    /// Only rules matched after this time, in milliseconds since the epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_time_stamp: Option<f64>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-TestMatchRequestDetails
+/// A simulated request for [`DeclarativeNetRequest::test_match_outcome`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMatchRequestDetails {
+    pub url: String,
+    /// The origin of the request. Without it, the request is matched as if
+    /// initiated by the browser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initiator: Option<String>,
+    /// Defaults to GET.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<RequestMethod>,
+    #[serde(rename = "type")]
+    pub type_: ResourceType,
+    /// Defaults to -1, i.e. a request that isn't related to a tab.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_id: Option<TabId>,
+}
+
+impl TestMatchRequestDetails {
+    pub fn new(url: impl Into<String>, type_: ResourceType) -> Self {
+        Self {
+            url: url.into(),
+            initiator: None,
+            method: None,
+            type_,
+            tab_id: None,
+        }
+    }
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RegexOptions
+/// A regular expression for [`DeclarativeNetRequest::is_regex_supported`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexOptions {
+    pub regex: String,
+    /// Defaults to true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_case_sensitive: Option<bool>,
+    /// Whether the regex is used with capturing groups, as in `regex_substitution`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_capturing: Option<bool>,
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-UnsupportedRegexReason
+    /// Why a regular expression is not supported.
+    UnsupportedRegexReason {
+        SyntaxError = "syntaxError",
+        MemoryLimitExceeded = "memoryLimitExceeded",
+    }
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-IsRegexSupportedResult
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsRegexSupportedResult {
+    pub is_supported: bool,
+    /// Set if the regex is not supported.
+    #[serde(default)]
+    pub reason: Option<UnsupportedRegexReason>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RequestDetails
+/// The request of a [`MatchedRuleInfoDebug`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugRequestDetails {
+    pub request_id: String,
+    pub url: String,
+    #[serde(default)]
+    pub initiator: Option<String>,
+    pub method: String,
+    pub frame_id: i32,
+    pub parent_frame_id: i32,
+    pub tab_id: TabId,
+    #[serde(rename = "type")]
+    pub type_: ResourceType,
+    #[serde(default)]
+    pub document_id: Option<String>,
+    #[serde(default)]
+    pub parent_document_id: Option<String>,
+    #[serde(default)]
+    pub document_lifecycle: Option<DocumentLifecycle>,
+    #[serde(default)]
+    pub frame_type: Option<FrameType>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-MatchedRuleInfoDebug
+/// The payload of `onRuleMatchedDebug`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedRuleInfoDebug {
+    pub request: DebugRequestDetails,
+    pub rule: MatchedRule,
+}
+
+impl MatchedRuleInfoDebug {
+    /// Converts the value a listener of
+    /// [`on_rule_matched_debug`](DeclarativeNetRequest::on_rule_matched_debug) receives.
+    pub fn from_js_value(value: &JsValue) -> Result<Self, JsValue> {
+        from_js(value)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateRuleOptions<'a> {
+    add_rules: &'a [Rule],
+    remove_rule_ids: &'a [u32],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateRulesetOptions<'a> {
+    enable_ruleset_ids: &'a [&'a str],
+    disable_ruleset_ids: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RulesMatchedDetails {
+    rules_matched_info: Vec<MatchedRuleInfo>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestMatchOutcomeResult {
+    matched_rules: Vec<MatchedRule>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/
+    pub type DeclarativeNetRequest;
+
+    #[wasm_bindgen(catch, method, js_name = updateDynamicRules)]
+    async fn update_dynamic_rules_js(
+        this: &DeclarativeNetRequest,
+        options: &JsValue,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = updateSessionRules)]
+    async fn update_session_rules_js(
+        this: &DeclarativeNetRequest,
+        options: &JsValue,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getDynamicRules)]
+    async fn get_dynamic_rules_js(this: &DeclarativeNetRequest) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getSessionRules)]
+    async fn get_session_rules_js(this: &DeclarativeNetRequest) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = updateEnabledRulesets)]
+    async fn update_enabled_rulesets_js(
+        this: &DeclarativeNetRequest,
+        options: &JsValue,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getEnabledRulesets)]
+    async fn get_enabled_rulesets_js(this: &DeclarativeNetRequest) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getMatchedRules)]
+    async fn get_matched_rules_js(
+        this: &DeclarativeNetRequest,
+        filter: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = testMatchOutcome)]
+    async fn test_match_outcome_js(
+        this: &DeclarativeNetRequest,
+        request: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = isRegexSupported)]
+    async fn is_regex_supported_js(
+        this: &DeclarativeNetRequest,
+        options: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    // Fired when a rule is matched with a request. Only available to unpacked
+    // extensions with the `declarativeNetRequestFeedback` permission. Convert
+    // the payload with `MatchedRuleInfoDebug::from_js_value`.
+    #[wasm_bindgen(method, getter, js_name = onRuleMatchedDebug)]
+    pub fn on_rule_matched_debug(this: &DeclarativeNetRequest) -> EventTarget;
+}
+
+impl DeclarativeNetRequest {
+    /// Removes the dynamic rules with the given ids and adds `add_rules`.
+    ///
+    /// The rules are validated before they are passed to the browser.
+    pub async fn update_dynamic_rules(
+        &self,
+        add_rules: &[Rule],
+        remove_rule_ids: &[u32],
+    ) -> Result<(), JsValue> {
+        validate_rules(add_rules)?;
+        let options = UpdateRuleOptions {
+            add_rules,
+            remove_rule_ids,
+        };
+        self.update_dynamic_rules_js(&to_js(&options)?).await
+    }
+
+    /// Removes the session rules with the given ids and adds `add_rules`.
+    ///
+    /// The rules are validated before they are passed to the browser.
+    pub async fn update_session_rules(
+        &self,
+        add_rules: &[Rule],
+        remove_rule_ids: &[u32],
+    ) -> Result<(), JsValue> {
+        validate_rules(add_rules)?;
+        let options = UpdateRuleOptions {
+            add_rules,
+            remove_rule_ids,
+        };
+        self.update_session_rules_js(&to_js(&options)?).await
+    }
+
+    /// Returns the current set of dynamic rules.
+    pub async fn get_dynamic_rules(&self) -> Result<Vec<Rule>, JsValue> {
+        from_js(&self.get_dynamic_rules_js().await?)
+    }
+
+    /// Returns the current set of session rules.
+    pub async fn get_session_rules(&self) -> Result<Vec<Rule>, JsValue> {
+        from_js(&self.get_session_rules_js().await?)
+    }
+
+    /// Enables and disables static rulesets by the ids of the manifest.
+    pub async fn update_enabled_rulesets(
+        &self,
+        enable_ruleset_ids: &[&str],
+        disable_ruleset_ids: &[&str],
+    ) -> Result<(), JsValue> {
+        let options = UpdateRulesetOptions {
+            enable_ruleset_ids,
+            disable_ruleset_ids,
+        };
+        self.update_enabled_rulesets_js(&to_js(&options)?).await
+    }
+
+    /// Returns the ids of the enabled static rulesets.
+    pub async fn get_enabled_rulesets(&self) -> Result<Vec<String>, JsValue> {
+        from_js(&self.get_enabled_rulesets_js().await?)
+    }
+
+    /// Returns the rules that matched requests since the extension was loaded.
+    ///
+    /// Requires the `declarativeNetRequestFeedback` permission, or
+    /// `activeTab` for the rules matched in a tab.
+    pub async fn get_matched_rules(
+        &self,
+        filter: &MatchedRulesFilter,
+    ) -> Result<Vec<MatchedRuleInfo>, JsValue> {
+        let details: RulesMatchedDetails =
+            from_js(&self.get_matched_rules_js(&to_js(filter)?).await?)?;
+        Ok(details.rules_matched_info)
+    }
+
+    /// Returns the rules of the extension that match the simulated request.
+    ///
+    /// Only available to unpacked extensions.
+    pub async fn test_match_outcome(
+        &self,
+        request: &TestMatchRequestDetails,
+    ) -> Result<Vec<MatchedRule>, JsValue> {
+        let result: TestMatchOutcomeResult =
+            from_js(&self.test_match_outcome_js(&to_js(request)?).await?)?;
+        Ok(result.matched_rules)
+    }
+
+    /// Checks whether the regular expression can be used as `regex_filter`.
+    pub async fn is_regex_supported(
+        &self,
+        options: &RegexOptions,
+    ) -> Result<IsRegexSupportedResult, JsValue> {
+        from_js(&self.is_regex_supported_js(&to_js(options)?).await?)
+    }
+}
@@ -0,0 +1,987 @@
+//! The rule model of the `declarativeNetRequest` API.
+
+use crate::{ResourceType, TabId};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, hash::Hash};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RuleActionType
+    /// What a matching rule does with the request.
+    RuleActionType {
+        Block = "block",
+        Redirect = "redirect",
+        Allow = "allow",
+        UpgradeScheme = "upgradeScheme",
+        ModifyHeaders = "modifyHeaders",
+        /// Allows all requests within a frame hierarchy, including the frame
+        /// request itself.
+        AllowAllRequests = "allowAllRequests",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-DomainType
+    /// Whether a request is first or third party to the frame it originates from.
+    DomainType {
+        FirstParty = "firstParty",
+        ThirdParty = "thirdParty",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RequestMethod
+    /// The HTTP request method of a network request.
+    RequestMethod {
+        Connect = "connect",
+        Delete = "delete",
+        Get = "get",
+        Head = "head",
+        Options = "options",
+        Patch = "patch",
+        Post = "post",
+        Put = "put",
+        Other = "other",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-HeaderOperation
+    /// How a header is modified by a `modifyHeaders` rule.
+    HeaderOperation {
+        Append = "append",
+        Set = "set",
+        Remove = "remove",
+    }
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-Rule
+/// A `declarativeNetRequest` rule.
+///
+/// A static ruleset file is the JSON serialization of a list of rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    /// An id which uniquely identifies the rule. Must be at least 1.
+    pub id: u32,
+    /// Rule priority. Defaults to 1; must be at least 1 when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    /// The action to take if the rule matches.
+    pub action: RuleAction,
+    /// The condition under which the rule matches.
+    #[serde(default)]
+    pub condition: RuleCondition,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RuleAction
+/// The action of a [`Rule`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleAction {
+    #[serde(rename = "type")]
+    pub type_: RuleActionType,
+    /// Where to redirect to. Only for [`RuleActionType::Redirect`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<Redirect>,
+    /// Request headers to modify. Only for [`RuleActionType::ModifyHeaders`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_headers: Option<Vec<ModifyHeaderInfo>>,
+    /// Response headers to modify. Only for [`RuleActionType::ModifyHeaders`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<Vec<ModifyHeaderInfo>>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-Redirect
+/// The target of a redirect rule. Exactly one field must be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Redirect {
+    /// Path relative to the extension directory. Must start with '/'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_path: Option<String>,
+    /// Substitution pattern for rules which specify a `regex_filter`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex_substitution: Option<String>,
+    /// Url transformations to perform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<UrlTransform>,
+    /// The redirect url.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-URLTransform
+/// Changes to the components of the request URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UrlTransform {
+    /// The new scheme, one of "http", "https", "ftp" and "chrome-extension".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The new port. The empty string removes the port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// The new path. The empty string removes the path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The new query, starting with '?'. The empty string removes the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Query key-value pairs to add or remove.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_transform: Option<QueryTransform>,
+    /// The new fragment, starting with '#'. The empty string removes the fragment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-QueryTransform
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct QueryTransform {
+    /// The query keys to remove.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove_params: Option<Vec<String>>,
+    /// The query key-value pairs to add or replace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_or_replace_params: Option<Vec<QueryKeyValue>>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-QueryKeyValue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryKeyValue {
+    pub key: String,
+    pub value: String,
+    /// Only replace the key if it is already present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace_only: Option<bool>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-ModifyHeaderInfo
+/// A header modification of a `modifyHeaders` rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyHeaderInfo {
+    /// The name of the header.
+    pub header: String,
+    pub operation: HeaderOperation,
+    /// The new value. Required for append and set, not allowed for remove.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+// https://developer.chrome.com/docs/extensions/reference/declarativeNetRequest/#type-RuleCondition
+/// The condition of a [`Rule`]. All given criteria must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RuleCondition {
+    /// The pattern which is matched against the request URL, e.g. `||example.com^`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_filter: Option<String>,
+    /// A RE2 regular expression matched against the request URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex_filter: Option<String>,
+    /// Whether the filter is case sensitive. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_url_filter_case_sensitive: Option<bool>,
+    /// Only matches requests originating from these domains or their subdomains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initiator_domains: Option<Vec<String>>,
+    /// Doesn't match requests originating from these domains or their subdomains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_initiator_domains: Option<Vec<String>>,
+    /// Only matches requests to these domains or their subdomains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_domains: Option<Vec<String>>,
+    /// Doesn't match requests to these domains or their subdomains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_request_domains: Option<Vec<String>>,
+    /// Only matches first or third party requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_type: Option<DomainType>,
+    /// Only matches these resource types.
+    ///
+    /// Without either resource type list, all types except `main_frame` match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_types: Option<Vec<ResourceType>>,
+    /// Doesn't match these resource types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_resource_types: Option<Vec<ResourceType>>,
+    /// Only matches these request methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_methods: Option<Vec<RequestMethod>>,
+    /// Doesn't match these request methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_request_methods: Option<Vec<RequestMethod>>,
+    /// Only matches requests of these tabs. Only for session rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab_ids: Option<Vec<TabId>>,
+    /// Doesn't match requests of these tabs. Only for session rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_tab_ids: Option<Vec<TabId>>,
+}
+
+impl Rule {
+    pub fn new(id: u32, action: RuleAction, condition: RuleCondition) -> Self {
+        Self {
+            id,
+            priority: None,
+            action,
+            condition,
+        }
+    }
+
+    /// Sets the priority of the rule.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Checks the rule for the invalid combinations the browser rejects.
+    ///
+    /// Regular expressions are not compiled; use
+    /// [`DeclarativeNetRequest::is_regex_supported`](crate::DeclarativeNetRequest::is_regex_supported)
+    /// for that.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        let error = |kind| RuleError {
+            rule_id: self.id,
+            kind,
+        };
+        if self.id == 0 {
+            return Err(error(RuleErrorKind::InvalidId));
+        }
+        if self.priority == Some(0) {
+            return Err(error(RuleErrorKind::InvalidPriority));
+        }
+        self.condition.validate().map_err(error)?;
+        self.action.validate(&self.condition).map_err(error)
+    }
+}
+
+impl RuleAction {
+    pub fn new(type_: RuleActionType) -> Self {
+        Self {
+            type_,
+            redirect: None,
+            request_headers: None,
+            response_headers: None,
+        }
+    }
+
+    /// Blocks the request.
+    pub fn block() -> Self {
+        Self::new(RuleActionType::Block)
+    }
+
+    /// Allows the request, overriding lower priority rules.
+    pub fn allow() -> Self {
+        Self::new(RuleActionType::Allow)
+    }
+
+    /// Allows all requests within the frame hierarchy of a matching frame request.
+    pub fn allow_all_requests() -> Self {
+        Self::new(RuleActionType::AllowAllRequests)
+    }
+
+    /// Upgrades the request to HTTPS.
+    pub fn upgrade_scheme() -> Self {
+        Self::new(RuleActionType::UpgradeScheme)
+    }
+
+    /// Redirects the request.
+    pub fn redirect(redirect: Redirect) -> Self {
+        Self {
+            redirect: Some(redirect),
+            ..Self::new(RuleActionType::Redirect)
+        }
+    }
+
+    /// Modifies the request and response headers.
+    pub fn modify_headers(
+        request_headers: Vec<ModifyHeaderInfo>,
+        response_headers: Vec<ModifyHeaderInfo>,
+    ) -> Self {
+        Self {
+            request_headers: (!request_headers.is_empty()).then_some(request_headers),
+            response_headers: (!response_headers.is_empty()).then_some(response_headers),
+            ..Self::new(RuleActionType::ModifyHeaders)
+        }
+    }
+
+    fn validate(&self, condition: &RuleCondition) -> Result<(), RuleErrorKind> {
+        check_known("type", [&self.type_], |type_| {
+            !matches!(type_, RuleActionType::Unknown(_))
+        })?;
+        match (&self.type_, &self.redirect) {
+            (RuleActionType::Redirect, None) => return Err(RuleErrorKind::MissingRedirect),
+            (RuleActionType::Redirect, Some(redirect)) => redirect.validate(condition)?,
+            (_, Some(_)) => return Err(RuleErrorKind::UnexpectedRedirect),
+            (_, None) => {}
+        }
+        let headers = [&self.request_headers, &self.response_headers];
+        if self.type_ == RuleActionType::ModifyHeaders {
+            let mut count = 0;
+            for header in headers.into_iter().flatten() {
+                if header.is_empty() {
+                    return Err(RuleErrorKind::EmptyList("headers"));
+                }
+                for info in header {
+                    info.validate()?;
+                }
+                count += header.len();
+            }
+            if count == 0 {
+                return Err(RuleErrorKind::MissingHeaders);
+            }
+        } else if headers.iter().any(|headers| headers.is_some()) {
+            return Err(RuleErrorKind::UnexpectedHeaders);
+        }
+        if self.type_ == RuleActionType::AllowAllRequests {
+            let frames_only = condition.resource_types.as_ref().is_some_and(|types| {
+                types
+                    .iter()
+                    .all(|type_| matches!(type_, ResourceType::MainFrame | ResourceType::SubFrame))
+            });
+            if !frames_only {
+                return Err(RuleErrorKind::InvalidAllowAllRequestsResourceTypes);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Redirect {
+    /// Redirects to the given URL.
+    pub fn url(url: impl Into<String>) -> Self {
+        Self {
+            url: Some(url.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Redirects to a file of the extension, e.g. `/blocked.html`.
+    pub fn extension_path(path: impl Into<String>) -> Self {
+        Self {
+            extension_path: Some(path.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Redirects to the URL built from the captures of the rule's `regex_filter`.
+    pub fn regex_substitution(substitution: impl Into<String>) -> Self {
+        Self {
+            regex_substitution: Some(substitution.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Redirects to the transformed request URL.
+    pub fn transform(transform: UrlTransform) -> Self {
+        Self {
+            transform: Some(transform),
+            ..Self::default()
+        }
+    }
+
+    fn validate(&self, condition: &RuleCondition) -> Result<(), RuleErrorKind> {
+        let targets = [
+            self.extension_path.is_some(),
+            self.regex_substitution.is_some(),
+            self.transform.is_some(),
+            self.url.is_some(),
+        ];
+        if targets.into_iter().filter(|&set| set).count() != 1 {
+            return Err(RuleErrorKind::InvalidRedirect);
+        }
+        if let Some(path) = &self.extension_path {
+            if !path.starts_with('/') {
+                return Err(RuleErrorKind::InvalidExtensionPath);
+            }
+        }
+        if self.regex_substitution.is_some() && condition.regex_filter.is_none() {
+            return Err(RuleErrorKind::RegexSubstitutionWithoutRegexFilter);
+        }
+        if let Some(url) = &self.url {
+            if !url.contains("://") {
+                return Err(RuleErrorKind::InvalidRedirectUrl);
+            }
+        }
+        if let Some(scheme) = self.transform.as_ref().and_then(|t| t.scheme.as_ref()) {
+            if !["http", "https", "ftp", "chrome-extension"].contains(&scheme.as_str()) {
+                return Err(RuleErrorKind::InvalidTransformScheme);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ModifyHeaderInfo {
+    /// Sets the header to `value`, replacing existing values.
+    pub fn set(header: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            operation: HeaderOperation::Set,
+            value: Some(value.into()),
+        }
+    }
+
+    /// Appends `value` to the header.
+    pub fn append(header: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            operation: HeaderOperation::Append,
+            value: Some(value.into()),
+        }
+    }
+
+    /// Removes the header.
+    pub fn remove(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            operation: HeaderOperation::Remove,
+            value: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), RuleErrorKind> {
+        if self.header.is_empty() {
+            return Err(RuleErrorKind::EmptyHeaderName);
+        }
+        check_known("operation", [&self.operation], |operation| {
+            !matches!(operation, HeaderOperation::Unknown(_))
+        })?;
+        let needs_value = self.operation != HeaderOperation::Remove;
+        if needs_value != self.value.is_some() {
+            return Err(RuleErrorKind::InvalidHeaderValue(self.header.clone()));
+        }
+        Ok(())
+    }
+}
+
+impl RuleCondition {
+    fn validate(&self) -> Result<(), RuleErrorKind> {
+        if self.url_filter.is_some() && self.regex_filter.is_some() {
+            return Err(RuleErrorKind::MultipleFilters);
+        }
+        if let Some(filter) = &self.url_filter {
+            if filter.is_empty() {
+                return Err(RuleErrorKind::EmptyUrlFilter);
+            }
+            if !filter.is_ascii() {
+                return Err(RuleErrorKind::NonAsciiUrlFilter);
+            }
+            if filter.starts_with("||*") {
+                return Err(RuleErrorKind::InvalidUrlFilter);
+            }
+        }
+        if self.regex_filter.as_deref() == Some("") {
+            return Err(RuleErrorKind::EmptyRegexFilter);
+        }
+        for (name, domains) in [
+            ("initiatorDomains", &self.initiator_domains),
+            ("excludedInitiatorDomains", &self.excluded_initiator_domains),
+            ("requestDomains", &self.request_domains),
+            ("excludedRequestDomains", &self.excluded_request_domains),
+        ] {
+            let Some(domains) = domains else { continue };
+            if domains.is_empty() && !name.starts_with("excluded") {
+                return Err(RuleErrorKind::EmptyList(name));
+            }
+            if let Some(domain) = domains.iter().find(|domain| !is_canonical_domain(domain)) {
+                return Err(RuleErrorKind::InvalidDomain(domain.clone()));
+            }
+        }
+        check_known("domainType", &self.domain_type, |domain_type| {
+            !matches!(domain_type, DomainType::Unknown(_))
+        })?;
+        let is_known_type = |type_: &ResourceType| !matches!(type_, ResourceType::Unknown(_));
+        check_known(
+            "resourceTypes",
+            self.resource_types.iter().flatten(),
+            is_known_type,
+        )?;
+        check_known(
+            "excludedResourceTypes",
+            self.excluded_resource_types.iter().flatten(),
+            is_known_type,
+        )?;
+        let is_known_method = |method: &RequestMethod| !matches!(method, RequestMethod::Unknown(_));
+        check_known(
+            "requestMethods",
+            self.request_methods.iter().flatten(),
+            is_known_method,
+        )?;
+        check_known(
+            "excludedRequestMethods",
+            self.excluded_request_methods.iter().flatten(),
+            is_known_method,
+        )?;
+        check_lists(
+            "resourceTypes",
+            &self.resource_types,
+            &self.excluded_resource_types,
+        )?;
+        check_lists(
+            "requestMethods",
+            &self.request_methods,
+            &self.excluded_request_methods,
+        )?;
+        check_lists("tabIds", &self.tab_ids, &self.excluded_tab_ids)
+    }
+}
+
+fn is_canonical_domain(domain: &str) -> bool {
+    !domain.is_empty()
+        && domain
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"-._[]:".contains(&b))
+}
+
+/// Rejects values that are unknown to this crate, and so to the browser.
+fn check_known<'a, T: AsRef<str> + 'a>(
+    field: &'static str,
+    values: impl IntoIterator<Item = &'a T>,
+    is_known: impl Fn(&T) -> bool,
+) -> Result<(), RuleErrorKind> {
+    match values.into_iter().find(|value| !is_known(value)) {
+        Some(value) => Err(RuleErrorKind::UnknownValue {
+            field,
+            value: value.as_ref().to_owned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn check_lists<T: Eq + Hash>(
+    name: &'static str,
+    included: &Option<Vec<T>>,
+    excluded: &Option<Vec<T>>,
+) -> Result<(), RuleErrorKind> {
+    if included.as_ref().is_some_and(Vec::is_empty) {
+        return Err(RuleErrorKind::EmptyList(name));
+    }
+    if let (Some(included), Some(excluded)) = (included, excluded) {
+        let excluded: HashSet<_> = excluded.iter().collect();
+        if included.iter().any(|value| excluded.contains(value)) {
+            return Err(RuleErrorKind::ConflictingLists(name));
+        }
+    }
+    Ok(())
+}
+
+/// Validates every rule and checks that the ids are unique.
+pub fn validate_rules(rules: &[Rule]) -> Result<(), RuleError> {
+    let mut ids = HashSet::with_capacity(rules.len());
+    for rule in rules {
+        rule.validate()?;
+        if !ids.insert(rule.id) {
+            return Err(RuleError {
+                rule_id: rule.id,
+                kind: RuleErrorKind::DuplicateId,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Validates the rules and serializes them as a static ruleset file.
+pub fn ruleset_json(rules: &[Rule]) -> Result<String, RuleError> {
+    validate_rules(rules)?;
+    Ok(serde_json::to_string_pretty(rules).expect("rules are always serializable"))
+}
+
+/// A rule that the browser would reject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    /// The id of the invalid rule.
+    pub rule_id: u32,
+    pub kind: RuleErrorKind,
+}
+
+/// Why a [`Rule`] is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleErrorKind {
+    /// The id is 0.
+    InvalidId,
+    /// The id is used by another rule of the same ruleset.
+    DuplicateId,
+    /// The priority is 0.
+    InvalidPriority,
+    /// Both `url_filter` and `regex_filter` are set.
+    MultipleFilters,
+    EmptyUrlFilter,
+    NonAsciiUrlFilter,
+    /// The `url_filter` starts with `||*`.
+    InvalidUrlFilter,
+    EmptyRegexFilter,
+    /// The `regex_filter`, or the regular expression of the `url_filter`, does not compile.
+    InvalidRegexFilter(String),
+    /// The named list must not be empty.
+    EmptyList(&'static str),
+    /// A value is both included and excluded by the named list.
+    ConflictingLists(&'static str),
+    /// A domain is empty or not in its lowercase, punycode-encoded form.
+    InvalidDomain(String),
+    /// A redirect rule has no `redirect`.
+    MissingRedirect,
+    /// A rule that doesn't redirect has a `redirect`.
+    UnexpectedRedirect,
+    /// Not exactly one redirect target is set.
+    InvalidRedirect,
+    /// The redirect URL is not absolute.
+    InvalidRedirectUrl,
+    /// The `extension_path` does not start with '/'.
+    InvalidExtensionPath,
+    /// A `regex_substitution` is used without a `regex_filter`.
+    RegexSubstitutionWithoutRegexFilter,
+    /// The transform sets a scheme other than http, https, ftp or chrome-extension.
+    InvalidTransformScheme,
+    /// A `modifyHeaders` rule modifies no header.
+    MissingHeaders,
+    /// A rule that doesn't modify headers has header modifications.
+    UnexpectedHeaders,
+    EmptyHeaderName,
+    /// The value of the named header is missing for append or set, or given for remove.
+    InvalidHeaderValue(String),
+    /// An `allowAllRequests` rule must only match `main_frame` and `sub_frame` requests.
+    InvalidAllowAllRequestsResourceTypes,
+    /// The named field has a value that the API doesn't define, e.g. an
+    /// `Unknown` action type or resource type.
+    UnknownValue {
+        field: &'static str,
+        value: String,
+    },
+}
+
+impl fmt::Display for RuleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId => f.write_str("the id must be at least 1"),
+            Self::DuplicateId => f.write_str("the id is not unique"),
+            Self::InvalidPriority => f.write_str("the priority must be at least 1"),
+            Self::MultipleFilters => {
+                f.write_str("only one of urlFilter and regexFilter can be specified")
+            }
+            Self::EmptyUrlFilter => f.write_str("urlFilter must not be empty"),
+            Self::NonAsciiUrlFilter => f.write_str("urlFilter must only contain ASCII characters"),
+            Self::InvalidUrlFilter => f.write_str("urlFilter must not start with '||*'"),
+            Self::EmptyRegexFilter => f.write_str("regexFilter must not be empty"),
+            Self::InvalidRegexFilter(error) => write!(f, "invalid regexFilter: {error}"),
+            Self::EmptyList(name) => write!(f, "{name} must not be empty"),
+            Self::ConflictingLists(name) => {
+                write!(f, "a value is both in {name} and its excluded list")
+            }
+            Self::InvalidDomain(domain) => write!(
+                f,
+                "domain {domain:?} must be non-empty, lowercase and punycode-encoded"
+            ),
+            Self::MissingRedirect => f.write_str("a redirect rule must specify a redirect"),
+            Self::UnexpectedRedirect => f.write_str("only redirect rules can specify a redirect"),
+            Self::InvalidRedirect => f.write_str("a redirect must specify exactly one target"),
+            Self::InvalidRedirectUrl => f.write_str("the redirect url must be an absolute URL"),
+            Self::InvalidExtensionPath => f.write_str("extensionPath must start with '/'"),
+            Self::RegexSubstitutionWithoutRegexFilter => {
+                f.write_str("regexSubstitution requires a regexFilter")
+            }
+            Self::InvalidTransformScheme => f.write_str(
+                "the transform scheme must be one of http, https, ftp and chrome-extension",
+            ),
+            Self::MissingHeaders => {
+                f.write_str("a modifyHeaders rule must modify at least one header")
+            }
+            Self::UnexpectedHeaders => {
+                f.write_str("only modifyHeaders rules can specify header modifications")
+            }
+            Self::EmptyHeaderName => f.write_str("the header name must not be empty"),
+            Self::InvalidHeaderValue(header) => write!(
+                f,
+                "header {header:?} needs a value to append or set and no value to remove"
+            ),
+            Self::InvalidAllowAllRequestsResourceTypes => f.write_str(
+                "an allowAllRequests rule must only match main_frame and sub_frame resource types",
+            ),
+            Self::UnknownValue { field, value } => write!(f, "{field} has unknown value {value:?}"),
+        }
+    }
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rule {}: {}", self.rule_id, self.kind)
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+impl From<RuleError> for wasm_bindgen::JsValue {
+    fn from(error: RuleError) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleEngine;
+    use serde_json::json;
+
+    fn condition(update: impl FnOnce(&mut RuleCondition)) -> RuleCondition {
+        let mut condition = RuleCondition::default();
+        update(&mut condition);
+        condition
+    }
+
+    fn block_if(update: impl FnOnce(&mut RuleCondition)) -> Rule {
+        Rule::new(1, RuleAction::block(), condition(update))
+    }
+
+    fn frames() -> RuleCondition {
+        condition(|c| c.resource_types = Some(vec![ResourceType::MainFrame]))
+    }
+
+    #[test]
+    fn valid_rules() {
+        let rules = [
+            block_if(|c| c.url_filter = Some("||example.com^".into())),
+            Rule::new(2, RuleAction::allow_all_requests(), frames()).with_priority(2),
+            Rule::new(
+                3,
+                RuleAction::redirect(Redirect::extension_path("/blocked.html")),
+                RuleCondition::default(),
+            ),
+            Rule::new(
+                4,
+                RuleAction::modify_headers(vec![ModifyHeaderInfo::remove("cookie")], vec![]),
+                RuleCondition::default(),
+            ),
+        ];
+        assert_eq!(validate_rules(&rules), Ok(()));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        use RuleErrorKind::*;
+        let unknown = |field: &'static str, value: &str| UnknownValue {
+            field,
+            value: value.to_owned(),
+        };
+        let with_action = |action| Rule::new(1, action, RuleCondition::default());
+        let headers_action = |info| RuleAction::modify_headers(vec![info], vec![]);
+        let cases = [
+            (
+                Rule::new(0, RuleAction::block(), RuleCondition::default()),
+                InvalidId,
+            ),
+            (block_if(|_| {}).with_priority(0), InvalidPriority),
+            (
+                block_if(|c| {
+                    c.url_filter = Some("a".into());
+                    c.regex_filter = Some("a".into());
+                }),
+                MultipleFilters,
+            ),
+            (block_if(|c| c.url_filter = Some("".into())), EmptyUrlFilter),
+            (
+                block_if(|c| c.url_filter = Some("ä".into())),
+                NonAsciiUrlFilter,
+            ),
+            (
+                block_if(|c| c.url_filter = Some("||*.com".into())),
+                InvalidUrlFilter,
+            ),
+            (
+                block_if(|c| c.regex_filter = Some("".into())),
+                EmptyRegexFilter,
+            ),
+            (
+                block_if(|c| c.request_domains = Some(vec![])),
+                EmptyList("requestDomains"),
+            ),
+            (
+                block_if(|c| {
+                    c.resource_types = Some(vec![ResourceType::Script]);
+                    c.excluded_resource_types = Some(vec![ResourceType::Script]);
+                }),
+                ConflictingLists("resourceTypes"),
+            ),
+            (
+                block_if(|c| c.initiator_domains = Some(vec!["Example.com".into()])),
+                InvalidDomain("Example.com".into()),
+            ),
+            (
+                with_action(RuleAction::new(RuleActionType::Redirect)),
+                MissingRedirect,
+            ),
+            (
+                with_action(RuleAction {
+                    redirect: Some(Redirect::url("https://example.com")),
+                    ..RuleAction::block()
+                }),
+                UnexpectedRedirect,
+            ),
+            (
+                with_action(RuleAction::redirect(Redirect::default())),
+                InvalidRedirect,
+            ),
+            (
+                with_action(RuleAction::redirect(Redirect::url("example.com"))),
+                InvalidRedirectUrl,
+            ),
+            (
+                with_action(RuleAction::redirect(Redirect::extension_path("a.html"))),
+                InvalidExtensionPath,
+            ),
+            (
+                with_action(RuleAction::redirect(Redirect::regex_substitution(r"\1"))),
+                RegexSubstitutionWithoutRegexFilter,
+            ),
+            (
+                with_action(RuleAction::redirect(Redirect::transform(UrlTransform {
+                    scheme: Some("gopher".into()),
+                    ..UrlTransform::default()
+                }))),
+                InvalidTransformScheme,
+            ),
+            (
+                with_action(RuleAction::modify_headers(vec![], vec![])),
+                MissingHeaders,
+            ),
+            (
+                with_action(RuleAction {
+                    request_headers: Some(vec![ModifyHeaderInfo::remove("cookie")]),
+                    ..RuleAction::block()
+                }),
+                UnexpectedHeaders,
+            ),
+            (
+                with_action(headers_action(ModifyHeaderInfo::set("", "a"))),
+                EmptyHeaderName,
+            ),
+            (
+                with_action(headers_action(ModifyHeaderInfo {
+                    value: Some("a".into()),
+                    ..ModifyHeaderInfo::remove("cookie")
+                })),
+                InvalidHeaderValue("cookie".into()),
+            ),
+            (
+                with_action(RuleAction::allow_all_requests()),
+                InvalidAllowAllRequestsResourceTypes,
+            ),
+            (
+                with_action(RuleAction::new(RuleActionType::from("blok"))),
+                unknown("type", "blok"),
+            ),
+            (
+                with_action(headers_action(ModifyHeaderInfo {
+                    operation: HeaderOperation::from("replace"),
+                    ..ModifyHeaderInfo::set("a", "b")
+                })),
+                unknown("operation", "replace"),
+            ),
+            (
+                block_if(|c| c.domain_type = Some(DomainType::from("x"))),
+                unknown("domainType", "x"),
+            ),
+            (
+                block_if(|c| c.resource_types = Some(vec![ResourceType::from("bogus")])),
+                unknown("resourceTypes", "bogus"),
+            ),
+            (
+                block_if(|c| c.excluded_resource_types = Some(vec![ResourceType::from("bogus")])),
+                unknown("excludedResourceTypes", "bogus"),
+            ),
+            (
+                block_if(|c| c.request_methods = Some(vec![RequestMethod::from("GET")])),
+                unknown("requestMethods", "GET"),
+            ),
+        ];
+        for (rule, kind) in cases {
+            assert_eq!(
+                rule.validate(),
+                Err(RuleError {
+                    rule_id: rule.id,
+                    kind
+                }),
+                "{rule:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let rule = block_if(|_| {});
+        assert_eq!(
+            validate_rules(&[rule.clone(), rule]),
+            Err(RuleError {
+                rule_id: 1,
+                kind: RuleErrorKind::DuplicateId
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_regex_filter() {
+        let rule = block_if(|c| c.regex_filter = Some("(".into()));
+        assert_eq!(rule.validate(), Ok(()));
+        let error = RuleEngine::new().add_ruleset("r", &[rule]).unwrap_err();
+        assert!(matches!(error.kind, RuleErrorKind::InvalidRegexFilter(_)));
+    }
+
+    #[test]
+    fn ruleset_json_round_trip() {
+        let rules = vec![
+            Rule::new(
+                1,
+                RuleAction::block(),
+                condition(|c| {
+                    c.url_filter = Some("||example.com^".into());
+                    c.resource_types = Some(vec![ResourceType::Script, ResourceType::SubFrame]);
+                    c.domain_type = Some(DomainType::ThirdParty);
+                }),
+            ),
+            Rule::new(
+                2,
+                RuleAction::modify_headers(vec![], vec![ModifyHeaderInfo::set("x-a", "b")]),
+                condition(|c| c.is_url_filter_case_sensitive = Some(true)),
+            )
+            .with_priority(3),
+        ];
+        let json = ruleset_json(&rules).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                {
+                    "id": 1,
+                    "action": { "type": "block" },
+                    "condition": {
+                        "urlFilter": "||example.com^",
+                        "domainType": "thirdParty",
+                        "resourceTypes": ["script", "sub_frame"]
+                    }
+                },
+                {
+                    "id": 2,
+                    "priority": 3,
+                    "action": {
+                        "type": "modifyHeaders",
+                        "responseHeaders": [
+                            { "header": "x-a", "operation": "set", "value": "b" }
+                        ]
+                    },
+                    "condition": { "isUrlFilterCaseSensitive": true }
+                }
+            ])
+        );
+        let parsed: Vec<Rule> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, rules);
+    }
+}
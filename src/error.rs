@@ -0,0 +1,126 @@
+//! Errors reported by the extension APIs.
+
+use crate::Runtime;
+use std::fmt;
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// An error returned by an extension API call, classified by its message.
+///
+/// Every variant keeps the original JS value, usually an `Error`, so
+/// nothing is lost when `?` converts the `JsValue` error of a `catch`
+/// binding into an `ExtensionError`.
+#[derive(Debug, Clone)]
+pub enum ExtensionError {
+    /// The message could not be delivered because nobody is listening,
+    /// e.g. the content script is not injected.
+    NoReceivingEnd(JsValue),
+    /// The requested tab does not exist (anymore).
+    TabNotFound(JsValue),
+    /// The extension lacks an API or host permission for the call.
+    MissingPermission(JsValue),
+    /// A storage quota or rate limit has been exceeded.
+    QuotaExceeded(JsValue),
+    /// The arguments of the call were rejected.
+    InvalidArgument(JsValue),
+    /// The extension has been reloaded or removed and this context is orphaned.
+    ContextInvalidated(JsValue),
+    /// Any other error.
+    Other(JsValue),
+}
+
+impl ExtensionError {
+    /// Returns the error reported by the last callback-based API call, if any.
+    pub fn last_error(runtime: &Runtime) -> Option<Self> {
+        runtime
+            .last_error()
+            .map(|error| Self::from(JsValue::from(error)))
+    }
+
+    /// The error message reported by the browser.
+    pub fn message(&self) -> String {
+        message_of(self.as_js_value())
+    }
+
+    /// The original JS value.
+    pub fn as_js_value(&self) -> &JsValue {
+        match self {
+            Self::NoReceivingEnd(value)
+            | Self::TabNotFound(value)
+            | Self::MissingPermission(value)
+            | Self::QuotaExceeded(value)
+            | Self::InvalidArgument(value)
+            | Self::ContextInvalidated(value)
+            | Self::Other(value) => value,
+        }
+    }
+
+    /// Consumes the error and returns the original JS value.
+    pub fn into_js_value(self) -> JsValue {
+        match self {
+            Self::NoReceivingEnd(value)
+            | Self::TabNotFound(value)
+            | Self::MissingPermission(value)
+            | Self::QuotaExceeded(value)
+            | Self::InvalidArgument(value)
+            | Self::ContextInvalidated(value)
+            | Self::Other(value) => value,
+        }
+    }
+}
+
+fn message_of(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        String::from(error.message())
+    } else if let Some(message) = value.as_string() {
+        message
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn classify(message: &str) -> fn(JsValue) -> ExtensionError {
+    let lowercase = message.to_lowercase();
+    let contains = |patterns: &[&str]| patterns.iter().any(|p| lowercase.contains(p));
+    if contains(&["extension context invalidated"]) {
+        ExtensionError::ContextInvalidated
+    } else if contains(&["receiving end does not exist"]) {
+        ExtensionError::NoReceivingEnd
+    } else if contains(&["no tab with id", "invalid tab id"]) {
+        ExtensionError::TabNotFound
+    } else if contains(&["quota", "max_write_operations", "max_items"]) {
+        ExtensionError::QuotaExceeded
+    } else if contains(&["permission", "cannot access"]) {
+        ExtensionError::MissingPermission
+    } else if contains(&[
+        "error in invocation of",
+        "invalid",
+        "value must",
+        "unexpected property",
+        "type error for parameter",
+        "incorrect argument",
+    ]) {
+        ExtensionError::InvalidArgument
+    } else {
+        ExtensionError::Other
+    }
+}
+
+impl From<JsValue> for ExtensionError {
+    fn from(value: JsValue) -> Self {
+        classify(&message_of(&value))(value)
+    }
+}
+
+impl From<ExtensionError> for JsValue {
+    fn from(error: ExtensionError) -> Self {
+        error.into_js_value()
+    }
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl std::error::Error for ExtensionError {}
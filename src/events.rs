@@ -0,0 +1,225 @@
+//! Types shared by the events of several APIs.
+
+use js_sys::{Array, Object};
+use std::ops::RangeInclusive;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/events/#type-UrlFilter
+    /// Filters URLs by their components.
+    ///
+    /// All criteria are case sensitive and must match.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type UrlFilter;
+
+    #[wasm_bindgen(method, setter = hostContains)]
+    fn set_host_contains(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = hostEquals)]
+    fn set_host_equals(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = hostPrefix)]
+    fn set_host_prefix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = hostSuffix)]
+    fn set_host_suffix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = pathContains)]
+    fn set_path_contains(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = pathEquals)]
+    fn set_path_equals(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = pathPrefix)]
+    fn set_path_prefix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = pathSuffix)]
+    fn set_path_suffix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = queryContains)]
+    fn set_query_contains(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = queryEquals)]
+    fn set_query_equals(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = queryPrefix)]
+    fn set_query_prefix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = querySuffix)]
+    fn set_query_suffix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = urlContains)]
+    fn set_url_contains(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = urlEquals)]
+    fn set_url_equals(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = urlMatches)]
+    fn set_url_matches(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = originAndPathMatches)]
+    fn set_origin_and_path_matches(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = urlPrefix)]
+    fn set_url_prefix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter = urlSuffix)]
+    fn set_url_suffix(this: &UrlFilter, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_schemes(this: &UrlFilter, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_ports(this: &UrlFilter, value: &Array);
+}
+
+impl UrlFilter {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Matches if the host name of the URL contains the string.
+    pub fn host_contains(&mut self, value: &str) -> &mut Self {
+        self.set_host_contains(value);
+        self
+    }
+
+    /// Matches if the host name of the URL is equal to the string.
+    pub fn host_equals(&mut self, value: &str) -> &mut Self {
+        self.set_host_equals(value);
+        self
+    }
+
+    /// Matches if the host name of the URL starts with the string.
+    pub fn host_prefix(&mut self, value: &str) -> &mut Self {
+        self.set_host_prefix(value);
+        self
+    }
+
+    /// Matches if the host name of the URL ends with the string.
+    pub fn host_suffix(&mut self, value: &str) -> &mut Self {
+        self.set_host_suffix(value);
+        self
+    }
+
+    /// Matches if the path segment of the URL contains the string.
+    pub fn path_contains(&mut self, value: &str) -> &mut Self {
+        self.set_path_contains(value);
+        self
+    }
+
+    /// Matches if the path segment of the URL is equal to the string.
+    pub fn path_equals(&mut self, value: &str) -> &mut Self {
+        self.set_path_equals(value);
+        self
+    }
+
+    /// Matches if the path segment of the URL starts with the string.
+    pub fn path_prefix(&mut self, value: &str) -> &mut Self {
+        self.set_path_prefix(value);
+        self
+    }
+
+    /// Matches if the path segment of the URL ends with the string.
+    pub fn path_suffix(&mut self, value: &str) -> &mut Self {
+        self.set_path_suffix(value);
+        self
+    }
+
+    /// Matches if the query segment of the URL contains the string.
+    pub fn query_contains(&mut self, value: &str) -> &mut Self {
+        self.set_query_contains(value);
+        self
+    }
+
+    /// Matches if the query segment of the URL is equal to the string.
+    pub fn query_equals(&mut self, value: &str) -> &mut Self {
+        self.set_query_equals(value);
+        self
+    }
+
+    /// Matches if the query segment of the URL starts with the string.
+    pub fn query_prefix(&mut self, value: &str) -> &mut Self {
+        self.set_query_prefix(value);
+        self
+    }
+
+    /// Matches if the query segment of the URL ends with the string.
+    pub fn query_suffix(&mut self, value: &str) -> &mut Self {
+        self.set_query_suffix(value);
+        self
+    }
+
+    /// Matches if the URL, without its fragment, contains the string.
+    pub fn url_contains(&mut self, value: &str) -> &mut Self {
+        self.set_url_contains(value);
+        self
+    }
+
+    /// Matches if the URL, without its fragment, is equal to the string.
+    pub fn url_equals(&mut self, value: &str) -> &mut Self {
+        self.set_url_equals(value);
+        self
+    }
+
+    /// Matches if the URL, without its fragment, matches the RE2 regular expression.
+    pub fn url_matches(&mut self, value: &str) -> &mut Self {
+        self.set_url_matches(value);
+        self
+    }
+
+    /// Matches if the URL without query and fragment matches the RE2 regular expression.
+    pub fn origin_and_path_matches(&mut self, value: &str) -> &mut Self {
+        self.set_origin_and_path_matches(value);
+        self
+    }
+
+    /// Matches if the URL, without its fragment, starts with the string.
+    pub fn url_prefix(&mut self, value: &str) -> &mut Self {
+        self.set_url_prefix(value);
+        self
+    }
+
+    /// Matches if the URL, without its fragment, ends with the string.
+    pub fn url_suffix(&mut self, value: &str) -> &mut Self {
+        self.set_url_suffix(value);
+        self
+    }
+
+    /// Matches if the scheme of the URL is one of the given schemes.
+    pub fn schemes<S: AsRef<str>>(&mut self, value: &[S]) -> &mut Self {
+        self.set_schemes(
+            &value
+                .iter()
+                .map(|value| JsValue::from_str(value.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Matches if the port of the URL is in one of the ranges.
+    ///
+    /// A single port is given as a range like `443..=443`.
+    pub fn ports(&mut self, value: &[RangeInclusive<u16>]) -> &mut Self {
+        let ports: Array = value
+            .iter()
+            .map(|range| -> JsValue {
+                if range.start() == range.end() {
+                    JsValue::from(*range.start())
+                } else {
+                    Array::of2(&(*range.start()).into(), &(*range.end()).into()).into()
+                }
+            })
+            .collect();
+        self.set_ports(&ports);
+        self
+    }
+}
+
+impl Default for UrlFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
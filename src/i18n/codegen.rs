@@ -0,0 +1,431 @@
+//! Typed accessors for the messages of an extension, generated at build time.
+//!
+//! Add this crate as a build dependency and write the accessors from the
+//! build script:
+//!
+//! ```text
+//! // build.rs
+//! fn main() {
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("messages.rs");
+//!     web_extensions_sys::codegen::write_messages("extension/_locales", "en", &out).unwrap();
+//! }
+//! ```
+//!
+//! and include them with `include!(concat!(env!("OUT_DIR"), "/messages.rs"));`.
+//! Every message of the default locale becomes a function named after its
+//! key, with one `&str` argument per `$1` to `$9` substitution, that calls
+//! `getMessage` on [`api()`](crate::api). The build fails if another locale
+//! has a message that the default locale lacks, or the number of
+//! substitutions differs between locales.
+
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    message: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    placeholders: BTreeMap<String, Placeholder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Placeholder {
+    content: String,
+}
+
+/// An error while generating the message accessors.
+#[derive(Debug)]
+pub enum CodegenError {
+    /// A file could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// A `messages.json` file is not valid.
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// The default locale has no `messages.json`.
+    MissingDefaultLocale(PathBuf),
+    /// A message of another locale is missing in the default locale.
+    MissingKey { locale: String, key: String },
+    /// A message has a different number of substitutions than in the default locale.
+    SubstitutionMismatch {
+        locale: String,
+        key: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Two keys of the default locale only differ in case or map to the same
+    /// function name, or a key is not a valid message name.
+    InvalidKey(String),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Json { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::MissingDefaultLocale(path) => {
+                write!(f, "the default locale {} does not exist", path.display())
+            }
+            Self::MissingKey { locale, key } => write!(
+                f,
+                "message {key:?} of locale {locale:?} is missing in the default locale"
+            ),
+            Self::SubstitutionMismatch {
+                locale,
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "message {key:?} of locale {locale:?} has {found} substitutions instead of {expected}"
+            ),
+            Self::InvalidKey(key) => write!(f, "invalid or duplicate message name {key:?}"),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Json { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn read_messages(path: &Path) -> Result<BTreeMap<String, Message>, CodegenError> {
+    let json = fs::read_to_string(path).map_err(|source| CodegenError::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+    let messages: BTreeMap<String, Message> =
+        serde_json::from_str(&json).map_err(|source| CodegenError::Json {
+            path: path.to_owned(),
+            source,
+        })?;
+    // Message names are case insensitive.
+    let mut normalized = BTreeMap::new();
+    for (key, message) in messages {
+        let lowercase = key.to_ascii_lowercase();
+        let valid = !key.is_empty()
+            && key
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'@');
+        if !valid || key.starts_with("@@") || normalized.contains_key(&lowercase) {
+            return Err(CodegenError::InvalidKey(key));
+        }
+        normalized.insert(lowercase, message);
+    }
+    Ok(normalized)
+}
+
+/// The highest `$1` to `$9` substitution of the message.
+fn substitutions(message: &Message) -> usize {
+    let mut max = 0;
+    let texts =
+        std::iter::once(&message.message).chain(message.placeholders.values().map(|p| &p.content));
+    for text in texts {
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '$' {
+                continue;
+            }
+            match chars.clone().next() {
+                Some('$') => {
+                    chars.next();
+                }
+                Some(digit @ '1'..='9') => {
+                    max = max.max(digit as usize - '0' as usize);
+                }
+                _ => {}
+            }
+        }
+    }
+    max
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+    "move", "mut", "priv", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+    "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+fn identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c == '@' {
+                '_'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn argument_names(message: &Message, count: usize) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(count);
+    for index in 1..=count {
+        let content = format!("${index}");
+        let name = message
+            .placeholders
+            .iter()
+            .find(|(_, placeholder)| placeholder.content == content)
+            .map(|(name, _)| identifier(name))
+            .filter(|name| !names.contains(name) && name != "api")
+            .unwrap_or_else(|| format!("arg{index}"));
+        names.push(name);
+    }
+    names
+}
+
+/// Generates the accessors for the messages in `locales_dir`.
+///
+/// `default_locale` is the `default_locale` of the manifest.
+pub fn generate_messages(
+    locales_dir: impl AsRef<Path>,
+    default_locale: &str,
+) -> Result<String, CodegenError> {
+    let locales_dir = locales_dir.as_ref();
+    let default_path = locales_dir.join(default_locale).join("messages.json");
+    if !default_path.is_file() {
+        return Err(CodegenError::MissingDefaultLocale(default_path));
+    }
+    let defaults = read_messages(&default_path)?;
+    let mut identifiers = BTreeMap::new();
+    for key in defaults.keys() {
+        // E.g. `foo@bar` and `foo_bar` would both become `fn foo_bar`.
+        if identifiers.insert(identifier(key), key).is_some() {
+            return Err(CodegenError::InvalidKey(key.clone()));
+        }
+    }
+    let counts: BTreeMap<&String, usize> = defaults
+        .iter()
+        .map(|(key, message)| (key, substitutions(message)))
+        .collect();
+
+    let entries = fs::read_dir(locales_dir).map_err(|source| CodegenError::Io {
+        path: locales_dir.to_owned(),
+        source,
+    })?;
+    let mut locales: Vec<_> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+    locales.sort();
+    for locale_dir in locales {
+        let path = locale_dir.join("messages.json");
+        let locale = locale_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if locale == default_locale || !path.is_file() {
+            continue;
+        }
+        for (key, message) in read_messages(&path)? {
+            let Some(&expected) = counts.get(&key) else {
+                return Err(CodegenError::MissingKey { locale, key });
+            };
+            let found = substitutions(&message);
+            if found != expected {
+                return Err(CodegenError::SubstitutionMismatch {
+                    locale,
+                    key,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    let mut code = format!(
+        "// Generated by web_extensions_sys::codegen from {:?}. Do not edit.\n",
+        default_path.display().to_string()
+    );
+    for (key, message) in &defaults {
+        let names = argument_names(message, counts[key]);
+        let doc = message.description.as_ref().unwrap_or(&message.message);
+        let params: Vec<_> = names.iter().map(|name| format!("{name}: &str")).collect();
+        let call = if names.is_empty() {
+            format!("get_message({key:?})")
+        } else {
+            format!(
+                "get_message_with_substitutions({key:?}, &[{}])",
+                names.join(", ")
+            )
+        };
+        // Writing to a `String` can't fail.
+        let _ = write!(
+            code,
+            "\n#[doc = {doc:?}]\npub fn {}({}) -> String {{\n    ::web_extensions_sys::api().i18n().{call}\n}}\n",
+            identifier(key),
+            params.join(", "),
+        );
+    }
+    Ok(code)
+}
+
+/// Writes the accessors for the messages in `locales_dir` to `out`.
+///
+/// Also tells Cargo to run the build script again when the messages change.
+pub fn write_messages(
+    locales_dir: impl AsRef<Path>,
+    default_locale: &str,
+    out: impl AsRef<Path>,
+) -> Result<(), CodegenError> {
+    let locales_dir = locales_dir.as_ref();
+    println!("cargo:rerun-if-changed={}", locales_dir.display());
+    let code = generate_messages(locales_dir, default_locale)?;
+    fs::write(out.as_ref(), code).map_err(|source| CodegenError::Io {
+        path: out.as_ref().to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `_locales` directory in the temporary directory, removed on drop.
+    struct Locales(PathBuf);
+
+    impl Locales {
+        fn new(name: &str, locales: &[(&str, &str)]) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "web-extensions-sys-codegen-{}-{name}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            for (locale, json) in locales {
+                fs::create_dir_all(dir.join(locale)).unwrap();
+                fs::write(dir.join(locale).join("messages.json"), json).unwrap();
+            }
+            Self(dir)
+        }
+
+        fn generate(&self) -> Result<String, CodegenError> {
+            generate_messages(&self.0, "en")
+        }
+    }
+
+    impl Drop for Locales {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn generated_signatures() {
+        let locales = Locales::new(
+            "signatures",
+            &[
+                (
+                    "en",
+                    r#"{
+                        "Greeting": {
+                            "message": "Hello $NAME$, you have $2 tabs",
+                            "description": "Greets the user",
+                            "placeholders": { "name": { "content": "$1" } }
+                        },
+                        "type": { "message": "Costs $$5" }
+                    }"#,
+                ),
+                (
+                    "de",
+                    r#"{ "greeting": { "message": "Hallo $1, $2 Tabs" } }"#,
+                ),
+            ],
+        );
+        let code = locales.generate().unwrap();
+        assert!(code.contains(
+            "#[doc = \"Greets the user\"]\npub fn greeting(name: &str, arg2: &str) -> String {\n    \
+             ::web_extensions_sys::api().i18n().get_message_with_substitutions(\"greeting\", &[name, arg2])\n}"
+        ));
+        assert!(code.contains(
+            "pub fn type_() -> String {\n    ::web_extensions_sys::api().i18n().get_message(\"type\")\n}"
+        ));
+    }
+
+    #[test]
+    fn missing_default_locale() {
+        let locales = Locales::new("missing-default", &[("de", r#"{}"#)]);
+        assert!(matches!(
+            locales.generate(),
+            Err(CodegenError::MissingDefaultLocale(path)) if path.ends_with("en/messages.json")
+        ));
+    }
+
+    #[test]
+    fn missing_key() {
+        let locales = Locales::new(
+            "missing-key",
+            &[
+                ("en", r#"{ "a": { "message": "A" } }"#),
+                (
+                    "fr",
+                    r#"{ "a": { "message": "A" }, "b": { "message": "B" } }"#,
+                ),
+            ],
+        );
+        assert!(matches!(
+            locales.generate(),
+            Err(CodegenError::MissingKey { locale, key }) if locale == "fr" && key == "b"
+        ));
+    }
+
+    #[test]
+    fn substitution_mismatch() {
+        let locales = Locales::new(
+            "substitution-mismatch",
+            &[
+                ("en", r#"{ "a": { "message": "$1 and $2" } }"#),
+                ("fr", r#"{ "a": { "message": "$1 et $$2" } }"#),
+            ],
+        );
+        assert!(matches!(
+            locales.generate(),
+            Err(CodegenError::SubstitutionMismatch { locale, key, expected: 2, found: 1 })
+                if locale == "fr" && key == "a"
+        ));
+    }
+
+    #[test]
+    fn invalid_keys() {
+        for (name, json) in [
+            (
+                "case",
+                r#"{ "foo": { "message": "" }, "FOO": { "message": "" } }"#,
+            ),
+            (
+                "identifier",
+                r#"{ "foo@bar": { "message": "" }, "foo_bar": { "message": "" } }"#,
+            ),
+            (
+                "keyword",
+                r#"{ "type": { "message": "" }, "type_": { "message": "" } }"#,
+            ),
+            ("character", r#"{ "foo-bar": { "message": "" } }"#),
+            ("predefined", r#"{ "@@ui_locale": { "message": "" } }"#),
+        ] {
+            let locales = Locales::new(name, &[("en", json)]);
+            assert!(
+                matches!(locales.generate(), Err(CodegenError::InvalidKey(_))),
+                "{name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Bindings to the `i18n` API.
+
+pub mod codegen;
+
+use js_sys::Array;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/i18n/#method-detectLanguage
+    #[derive(Debug, Clone)]
+    pub type DetectedLanguage;
+
+    // The ISO language code, e.g. "en" or "fr".
+    #[wasm_bindgen(method, getter)]
+    pub fn language(this: &DetectedLanguage) -> String;
+
+    // The percentage of the detected language.
+    #[wasm_bindgen(method, getter)]
+    pub fn percentage(this: &DetectedLanguage) -> u32;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/i18n/#method-detectLanguage
+    #[derive(Debug, Clone)]
+    pub type LanguageDetectionResult;
+
+    // Whether the detected language is reliable.
+    #[wasm_bindgen(method, getter, js_name = isReliable)]
+    pub fn is_reliable(this: &LanguageDetectionResult) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = languages)]
+    fn languages_js(this: &LanguageDetectionResult) -> Array;
+}
+
+impl LanguageDetectionResult {
+    /// The detected languages, at most three.
+    pub fn languages(&self) -> Vec<DetectedLanguage> {
+        self.languages_js()
+            .iter()
+            .map(JsCast::unchecked_into)
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/i18n/
+    pub type I18n;
+
+    // Gets the localized string for the message. Returns an empty string if
+    // the message is missing.
+    #[wasm_bindgen(method, js_name = getMessage)]
+    pub fn get_message(this: &I18n, message_name: &str) -> String;
+
+    #[wasm_bindgen(method, js_name = getMessage)]
+    fn get_message_with_substitutions_js(
+        this: &I18n,
+        message_name: &str,
+        substitutions: &Array,
+    ) -> String;
+
+    // Gets the browser UI language, e.g. "en-US".
+    #[wasm_bindgen(method, js_name = getUILanguage)]
+    pub fn get_ui_language(this: &I18n) -> String;
+
+    #[wasm_bindgen(catch, method, js_name = getAcceptLanguages)]
+    async fn get_accept_languages_js(this: &I18n) -> Result<Array, JsValue>;
+
+    // Detects the language of the text.
+    #[wasm_bindgen(catch, method, js_name = detectLanguage)]
+    pub async fn detect_language(
+        this: &I18n,
+        text: &str,
+    ) -> Result<LanguageDetectionResult, JsValue>;
+}
+
+impl I18n {
+    /// Gets the localized string for the message with the values of the
+    /// placeholders `$1` to `$9`.
+    pub fn get_message_with_substitutions<S: AsRef<str>>(
+        &self,
+        message_name: &str,
+        substitutions: &[S],
+    ) -> String {
+        let substitutions = substitutions
+            .iter()
+            .map(|value| JsValue::from_str(value.as_ref()))
+            .collect();
+        self.get_message_with_substitutions_js(message_name, &substitutions)
+    }
+
+    /// Gets the accept-languages of the browser.
+    pub async fn get_accept_languages(&self) -> Result<Vec<String>, JsValue> {
+        let languages = self.get_accept_languages_js().await?;
+        Ok(languages
+            .iter()
+            .filter_map(|language| language.as_string())
+            .collect())
+    }
+}
@@ -0,0 +1,54 @@
+//! Bindings to the `idle` API.
+
+use crate::EventTarget;
+use wasm_bindgen::prelude::*;
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/idle/#type-IdleState
+    /// Whether the user is active.
+    IdleState {
+        Active = "active",
+        /// No input for the detection interval.
+        Idle = "idle",
+        /// The screen is locked or the screensaver is active.
+        Locked = "locked",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/idle/
+    pub type Idle;
+
+    #[wasm_bindgen(catch, method, js_name = queryState)]
+    async fn query_state_js(this: &Idle, detection_interval: u32) -> Result<JsValue, JsValue>;
+
+    // Sets the interval in seconds after which `onStateChanged` reports the
+    // idle state. Defaults to 60, the minimum is 15.
+    #[wasm_bindgen(method, js_name = setDetectionInterval)]
+    pub fn set_detection_interval(this: &Idle, interval: u32);
+
+    #[wasm_bindgen(catch, method, js_name = getAutoLockDelay)]
+    async fn get_auto_lock_delay_js(this: &Idle) -> Result<JsValue, JsValue>;
+
+    // Fired when the idle state changes. The listener receives the state as a string,
+    // see `IdleState`.
+    #[wasm_bindgen(method, getter, js_name = onStateChanged)]
+    pub fn on_state_changed(this: &Idle) -> EventTarget;
+}
+
+impl Idle {
+    /// Returns `Locked` if the screen is locked, `Idle` if the user made no
+    /// input for `detection_interval` seconds, and `Active` otherwise.
+    pub async fn query_state(&self, detection_interval: u32) -> Result<IdleState, JsValue> {
+        let state = self.query_state_js(detection_interval).await?;
+        Ok(IdleState::from(state.as_string().unwrap_or_default()))
+    }
+
+    /// The time in seconds after which the screen locks automatically, or 0
+    /// if it never does (ChromeOS only).
+    pub async fn get_auto_lock_delay(&self) -> Result<u32, JsValue> {
+        let delay = self.get_auto_lock_delay_js().await?;
+        Ok(delay.as_f64().unwrap_or_default() as u32)
+    }
+}
@@ -0,0 +1,20 @@
+//! Conversion between serde types and JS values through JSON.
+
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsValue;
+
+fn json_error(error: serde_json::Error) -> JsValue {
+    js_sys::Error::new(&error.to_string()).into()
+}
+
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+    js_sys::JSON::parse(&serde_json::to_string(value).map_err(json_error)?)
+}
+
+pub(crate) fn from_js<T: DeserializeOwned>(value: &JsValue) -> Result<T, JsValue> {
+    // `JSON.stringify` returns `undefined` for `undefined` and functions.
+    let json = js_sys::JSON::stringify(value)?
+        .as_string()
+        .ok_or_else(|| js_sys::Error::new("the value has no JSON representation"))?;
+    serde_json::from_str(&json).map_err(json_error)
+}
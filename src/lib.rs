@@ -1,92 +1,182 @@
 #![doc = include_str!("../README.md")]
 
-use js_sys::Function;
-use wasm_bindgen::{prelude::*, JsStatic};
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[macro_use]
+mod macros;
 
 mod action;
+mod alarms;
 mod bookmarks;
-#[cfg(feature = "firefox")]
 mod browser_action;
 mod commands;
-#[cfg(feature = "firefox")]
+mod context_menus;
 mod contextual_identities;
+mod cookies;
+mod declarative_net_request;
 mod downloads;
+mod error;
+mod events;
 mod history;
+mod i18n;
 mod identity;
+mod idle;
+mod json;
+mod listener;
+mod management;
+mod notifications;
+mod offscreen;
 mod omnibox;
+mod permissions;
 mod port;
+mod power;
 mod runtime;
 mod scripting;
 mod sessions;
-#[cfg(feature = "firefox")]
+mod side_panel;
 mod sidebar_action;
 mod storage;
+mod stream;
+mod tab_groups;
 mod tabs;
-#[cfg(feature = "firefox")]
 mod theme;
+mod top_sites;
+mod web_navigation;
+mod web_request;
 mod windows;
 
 pub use action::*;
+pub use alarms::*;
 pub use bookmarks::*;
-#[cfg(feature = "firefox")]
 pub use browser_action::*;
 pub use commands::*;
-#[cfg(feature = "firefox")]
+pub use context_menus::*;
 pub use contextual_identities::*;
+pub use cookies::*;
+pub use declarative_net_request::*;
 pub use downloads::*;
+pub use error::*;
+pub use events::*;
 pub use history::*;
+pub use i18n::*;
 pub use identity::*;
+pub use idle::*;
+pub use listener::*;
+pub use management::*;
+pub use notifications::*;
+pub use offscreen::*;
 pub use omnibox::*;
+pub use permissions::*;
 pub use port::*;
+pub use power::*;
 pub use runtime::*;
 pub use scripting::*;
 pub use sessions::*;
-#[cfg(feature = "firefox")]
+pub use side_panel::*;
 pub use sidebar_action::*;
 pub use storage::*;
+pub use stream::*;
+pub use tab_groups::*;
 pub use tabs::*;
-#[cfg(feature = "firefox")]
 pub use theme::*;
+pub use top_sites::*;
+pub use web_navigation::*;
+pub use web_request::*;
 pub use windows::*;
 
 pub mod traits {
     pub use crate::storage::{StorageArea, StorageAreaRead};
 }
 
-#[cfg(feature = "firefox")]
-pub fn browser() -> &'static JsStatic<Browser> {
-    &BROWSER
+/// The kind of browser the extension is running in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BrowserKind {
+    /// Chrome, Edge and other Chromium based browsers.
+    Chromium,
+    /// Mozilla Firefox.
+    Firefox,
+    /// Safari.
+    Safari,
+}
+
+thread_local! {
+    static DETECTED: (BrowserKind, Browser) = detect();
+}
+
+fn property(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &JsValue::from_str(key))
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+fn detect() -> (BrowserKind, Browser) {
+    match property(&js_sys::global(), "browser") {
+        Some(browser) => {
+            let is_firefox = property(&browser, "runtime")
+                .and_then(|runtime| property(&runtime, "getBrowserInfo"))
+                .is_some_and(|get_browser_info| get_browser_info.is_function());
+            let kind = if is_firefox {
+                BrowserKind::Firefox
+            } else {
+                BrowserKind::Safari
+            };
+            (kind, browser.unchecked_into())
+        }
+        None => (BrowserKind::Chromium, chrome()),
+    }
+}
+
+/// The extension API of the running browser.
+///
+/// This is the `browser` global where it exists (Firefox, Safari) and the
+/// `chrome` global otherwise, so the same build works in every browser.
+pub fn api() -> Browser {
+    DETECTED.with(|(_, browser)| browser.clone())
+}
+
+/// The kind of browser the extension is running in.
+pub fn browser_kind() -> BrowserKind {
+    DETECTED.with(|(kind, _)| *kind)
+}
+
+/// The `browser` global. It only exists in Firefox and Safari; prefer [`api`].
+pub fn browser() -> Browser {
+    BROWSER.with(Browser::clone)
 }
 
-#[cfg(not(feature = "firefox"))]
-pub fn chrome() -> &'static JsStatic<Browser> {
-    &CHROME
+/// The `chrome` global. Prefer [`api`].
+pub fn chrome() -> Browser {
+    CHROME.with(Browser::clone)
 }
 
 #[wasm_bindgen]
 extern "C" {
-    pub type Browser;
-
-    // This is used for Mozilla Firefox Addons
-    #[cfg(feature = "firefox")]
-    #[wasm_bindgen(js_name = browser)]
+    #[wasm_bindgen(thread_local_v2, js_name = browser)]
     static BROWSER: Browser;
 
-    // This is used for Google Chrome Extensions
-    #[cfg(not(feature = "firefox"))]
-    #[wasm_bindgen(js_name = chrome)]
+    #[wasm_bindgen(thread_local_v2, js_name = chrome)]
     static CHROME: Browser;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    pub type Browser;
 
     #[wasm_bindgen(method, getter)]
     pub fn action(this: &Browser) -> Action;
 
-    #[cfg(feature = "firefox")]
+    #[wasm_bindgen(method, getter)]
+    pub fn alarms(this: &Browser) -> Alarms;
+
+    // Firefox only
     #[wasm_bindgen(method, getter, js_name = browserAction)]
-    pub fn browser_action(this: &Browser) -> BrowserAction;
+    pub fn browser_action(this: &Browser) -> Option<BrowserAction>;
 
-    #[cfg(feature = "firefox")]
+    // Firefox only
     #[wasm_bindgen(method, getter, js_name = contextualIdentities)]
-    pub fn contextual_identities(this: &Browser) -> ContextualIdentities;
+    pub fn contextual_identities(this: &Browser) -> Option<ContextualIdentities>;
 
     #[wasm_bindgen(method, getter)]
     pub fn downloads(this: &Browser) -> Downloads;
@@ -97,9 +187,13 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn sessions(this: &Browser) -> Sessions;
 
-    #[cfg(feature = "firefox")]
+    // Firefox only
     #[wasm_bindgen(method, getter, js_name = sidebarAction)]
-    pub fn sidebar_action(this: &Browser) -> SidebarAction;
+    pub fn sidebar_action(this: &Browser) -> Option<SidebarAction>;
+
+    // Chromium only
+    #[wasm_bindgen(method, getter, js_name = sidePanel)]
+    pub fn side_panel(this: &Browser) -> Option<SidePanel>;
 
     #[wasm_bindgen(method, getter)]
     pub fn storage(this: &Browser) -> Storage;
@@ -107,9 +201,13 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn tabs(this: &Browser) -> Tabs;
 
-    #[cfg(feature = "firefox")]
+    // Chromium and Firefox 139+
+    #[wasm_bindgen(method, getter, js_name = tabGroups)]
+    pub fn tab_groups(this: &Browser) -> Option<TabGroups>;
+
+    // Firefox only
     #[wasm_bindgen(method, getter)]
-    pub fn theme(this: &Browser) -> BrowserTheme;
+    pub fn theme(this: &Browser) -> Option<BrowserTheme>;
 
     #[wasm_bindgen(method, getter)]
     pub fn windows(this: &Browser) -> Windows;
@@ -126,20 +224,77 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn commands(this: &Browser) -> Commands;
 
+    #[wasm_bindgen(method, getter, js_name = contextMenus)]
+    pub fn context_menus(this: &Browser) -> ContextMenus;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn cookies(this: &Browser) -> Cookies;
+
+    #[wasm_bindgen(method, getter, js_name = declarativeNetRequest)]
+    pub fn declarative_net_request(this: &Browser) -> DeclarativeNetRequest;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn i18n(this: &Browser) -> I18n;
+
     #[wasm_bindgen(method, getter)]
     pub fn identity(this: &Browser) -> Identity;
 
+    #[wasm_bindgen(method, getter)]
+    pub fn idle(this: &Browser) -> Idle;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn management(this: &Browser) -> Management;
+
+    // Chromium only
+    #[wasm_bindgen(method, getter)]
+    pub fn offscreen(this: &Browser) -> Option<Offscreen>;
+
     #[wasm_bindgen(method, getter)]
     pub fn omnibox(this: &Browser) -> Omnibox;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn permissions(this: &Browser) -> BrowserPermissions;
+
+    // Chromium only
+    #[wasm_bindgen(method, getter)]
+    pub fn power(this: &Browser) -> Option<Power>;
+
+    #[wasm_bindgen(method, getter, js_name = topSites)]
+    pub fn top_sites(this: &Browser) -> TopSites;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn notifications(this: &Browser) -> Notifications;
+
+    #[wasm_bindgen(method, getter, js_name = webNavigation)]
+    pub fn web_navigation(this: &Browser) -> WebNavigation;
+
+    #[wasm_bindgen(method, getter, js_name = webRequest)]
+    pub fn web_request(this: &Browser) -> WebRequest;
 }
 
 #[wasm_bindgen]
 extern "C" {
+    #[derive(Debug, Clone)]
     pub type EventTarget;
 
     #[wasm_bindgen(method, js_name = addListener)]
     pub fn add_listener(this: &EventTarget, listener: &Function);
 
+    // Registers a listener for an event that supports filters, e.g. the
+    // `webNavigation` events.
+    #[wasm_bindgen(method, js_name = addListener)]
+    pub fn add_listener_with_filter(this: &EventTarget, listener: &Function, filter: &Object);
+
+    // Registers a listener with a filter and additional options, e.g. the
+    // `extraInfoSpec` of the `webRequest` events.
+    #[wasm_bindgen(method, js_name = addListener)]
+    pub fn add_listener_with_options(
+        this: &EventTarget,
+        listener: &Function,
+        filter: &Object,
+        options: &Array,
+    );
+
     #[wasm_bindgen(method, js_name = removeListener)]
     pub fn remove_listener(this: &EventTarget, listener: &Function);
 
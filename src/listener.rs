@@ -0,0 +1,98 @@
+//! Owned event listeners that unregister themselves when dropped.
+
+use crate::EventTarget;
+use js_sys::{Array, Object};
+use std::fmt;
+use wasm_bindgen::{closure::WasmClosure, prelude::*, JsCast};
+
+/// A listener registered with [`EventTarget::listen`].
+///
+/// The handle owns the closure and keeps it alive while it is registered.
+/// Dropping the handle calls `removeListener` before the closure is freed,
+/// so the browser can never invoke a dropped closure.
+#[must_use = "dropping a `ListenerHandle` removes the listener immediately"]
+pub struct ListenerHandle<T: ?Sized + WasmClosure> {
+    target: EventTarget,
+    closure: Option<Closure<T>>,
+}
+
+impl<T: ?Sized + WasmClosure> ListenerHandle<T> {
+    pub(crate) fn new(target: EventTarget, closure: Closure<T>) -> Self {
+        Self {
+            target,
+            closure: Some(closure),
+        }
+    }
+
+    /// The event this listener is registered with.
+    pub fn target(&self) -> &EventTarget {
+        &self.target
+    }
+
+    /// Keeps the listener registered for the rest of the program.
+    ///
+    /// The closure is leaked, which is what listeners registered at the top
+    /// level of a background script usually want.
+    pub fn detach(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+}
+
+impl<T: ?Sized + WasmClosure> Drop for ListenerHandle<T> {
+    fn drop(&mut self) {
+        if let Some(closure) = self.closure.take() {
+            self.target
+                .remove_listener(closure.as_ref().unchecked_ref());
+        }
+    }
+}
+
+impl<T: ?Sized + WasmClosure> fmt::Debug for ListenerHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListenerHandle")
+            .field("target", &self.target)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventTarget {
+    /// Registers `closure` as a listener and returns a handle that owns it.
+    ///
+    /// The listener stays registered until the handle is dropped or
+    /// [`detach`](ListenerHandle::detach)ed.
+    pub fn listen<T: ?Sized + WasmClosure>(&self, closure: Closure<T>) -> ListenerHandle<T> {
+        self.add_listener(closure.as_ref().unchecked_ref());
+        ListenerHandle::new(self.clone(), closure)
+    }
+
+    /// Like [`listen`](Self::listen), but only events that match `filter`
+    /// invoke the listener.
+    pub fn listen_with_filter<T: ?Sized + WasmClosure>(
+        &self,
+        closure: Closure<T>,
+        filter: &Object,
+    ) -> ListenerHandle<T> {
+        self.add_listener_with_filter(closure.as_ref().unchecked_ref(), filter);
+        ListenerHandle::new(self.clone(), closure)
+    }
+
+    /// Like [`listen_with_filter`](Self::listen_with_filter), with the
+    /// additional options some events take as third argument, e.g. a list
+    /// of [`ExtraInfoSpec`](crate::ExtraInfoSpec)s.
+    pub fn listen_with_options<T, O>(
+        &self,
+        closure: Closure<T>,
+        filter: &Object,
+        options: &[O],
+    ) -> ListenerHandle<T>
+    where
+        T: ?Sized + WasmClosure,
+        O: Clone + Into<JsValue>,
+    {
+        let options: Array = options.iter().cloned().map(Into::into).collect();
+        self.add_listener_with_options(closure.as_ref().unchecked_ref(), filter, &options);
+        ListenerHandle::new(self.clone(), closure)
+    }
+}
@@ -0,0 +1,177 @@
+/// Declares a newtype around a JS number that can be used in bindings.
+///
+/// The value crosses the wasm boundary as a `JsValue`, which lets
+/// `Option<$name>` map `None` to `undefined` like any other optional binding.
+macro_rules! js_number_newtype {
+    ($(#[$meta:meta])* $name:ident($repr:ty)) => {
+        $(#[$meta])*
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub $repr);
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl From<$repr> for $name {
+            fn from(value: $repr) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $repr {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl From<$name> for wasm_bindgen::JsValue {
+            fn from(value: $name) -> Self {
+                Self::from(value.0)
+            }
+        }
+
+        impl crate::EventArg<crate::ConvertArg> for $name {
+            fn from_arg(value: wasm_bindgen::JsValue) -> Option<Self> {
+                <$repr as crate::EventArg<crate::ConvertArg>>::from_arg(value).map(Self)
+            }
+        }
+
+        impl wasm_bindgen::describe::WasmDescribe for $name {
+            fn describe() {
+                <wasm_bindgen::JsValue as wasm_bindgen::describe::WasmDescribe>::describe()
+            }
+        }
+
+        impl wasm_bindgen::convert::IntoWasmAbi for $name {
+            type Abi = <wasm_bindgen::JsValue as wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+            fn into_abi(self) -> Self::Abi {
+                wasm_bindgen::JsValue::from(self).into_abi()
+            }
+        }
+
+        impl wasm_bindgen::convert::FromWasmAbi for $name {
+            type Abi = <wasm_bindgen::JsValue as wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                let value = <wasm_bindgen::JsValue as wasm_bindgen::convert::FromWasmAbi>::from_abi(js);
+                // A missing or non-numeric value raises a JS exception instead
+                // of decoding as a made-up id; use `Option<$name>` for
+                // properties that can be absent.
+                match value.as_f64() {
+                    Some(number) => Self(number as $repr),
+                    None => wasm_bindgen::throw_str(concat!(stringify!($name), " is not a number")),
+                }
+            }
+        }
+
+        impl wasm_bindgen::convert::OptionIntoWasmAbi for $name {
+            fn none() -> Self::Abi {
+                <wasm_bindgen::JsValue as wasm_bindgen::convert::OptionIntoWasmAbi>::none()
+            }
+        }
+
+        impl wasm_bindgen::convert::OptionFromWasmAbi for $name {
+            fn is_none(abi: &Self::Abi) -> bool {
+                <wasm_bindgen::JsValue as wasm_bindgen::convert::OptionFromWasmAbi>::is_none(abi)
+            }
+        }
+    };
+}
+
+/// Declares an enum for a string-typed property of the extension API.
+///
+/// Values that are unknown to this crate are kept in an `Unknown` variant,
+/// so new browser versions don't break deserialization.
+macro_rules! js_string_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A value that is not known to this crate.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value used by the extension API.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(value.to_owned()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.as_str() {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(value),
+                }
+            }
+        }
+
+        impl crate::EventArg<crate::ConvertArg> for $name {
+            fn from_arg(value: wasm_bindgen::JsValue) -> Option<Self> {
+                <String as crate::EventArg<crate::ConvertArg>>::from_arg(value).map(Self::from)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Unknown(value) => value,
+                    value => value.as_str().to_owned(),
+                }
+            }
+        }
+
+        impl From<$name> for wasm_bindgen::JsValue {
+            fn from(value: $name) -> Self {
+                Self::from_str(value.as_str())
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
@@ -0,0 +1,279 @@
+//! Bindings to the `management` API.
+
+use crate::EventTarget;
+use js_sys::{Array, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/management/#type-ExtensionInstallType
+    /// How an extension was installed.
+    ExtensionInstallType {
+        /// Installed because of an administrative policy.
+        Admin = "admin",
+        /// Loaded unpacked in developer mode.
+        Development = "development",
+        /// Installed normally via a .crx file or the store.
+        Normal = "normal",
+        /// Installed by other software on the machine.
+        Sideload = "sideload",
+        Other = "other",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/management/#type-ExtensionDisabledReason
+    /// Why an extension is disabled.
+    ExtensionDisabledReason {
+        /// The browser reports no specific reason (`"unknown"`).
+        Unspecified = "unknown",
+        /// The update requires permissions the user has not accepted yet.
+        PermissionsIncrease = "permissions_increase",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/management/#type-ExtensionType
+    /// The type of an installed add-on.
+    ExtensionType {
+        Extension = "extension",
+        HostedApp = "hosted_app",
+        PackagedApp = "packaged_app",
+        LegacyPackagedApp = "legacy_packaged_app",
+        Theme = "theme",
+        LoginScreenExtension = "login_screen_extension",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/management/#type-IconInfo
+    #[derive(Debug, Clone)]
+    pub type IconInfo;
+
+    // The width and height of the icon in pixels.
+    #[wasm_bindgen(method, getter)]
+    pub fn size(this: &IconInfo) -> u32;
+
+    // The URL of the icon.
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &IconInfo) -> String;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/management/#type-ExtensionInfo
+    /// Information about an installed extension, app or theme.
+    #[derive(Debug, Clone)]
+    pub type ExtensionInfo;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn id(this: &ExtensionInfo) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &ExtensionInfo) -> String;
+
+    // The short name, or the name if there is none (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = shortName)]
+    pub fn short_name(this: &ExtensionInfo) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn description(this: &ExtensionInfo) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn version(this: &ExtensionInfo) -> String;
+
+    // The version for display purposes.
+    #[wasm_bindgen(method, getter, js_name = versionName)]
+    pub fn version_name(this: &ExtensionInfo) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn enabled(this: &ExtensionInfo) -> bool;
+
+    // Whether the user may disable or uninstall the extension.
+    #[wasm_bindgen(method, getter, js_name = mayDisable)]
+    pub fn may_disable(this: &ExtensionInfo) -> bool;
+
+    // Whether the user may enable the extension. Only set for disabled extensions (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = mayEnable)]
+    pub fn may_enable(this: &ExtensionInfo) -> Option<bool>;
+
+    #[wasm_bindgen(method, getter, js_name = homepageUrl)]
+    pub fn homepage_url(this: &ExtensionInfo) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = updateUrl)]
+    pub fn update_url(this: &ExtensionInfo) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = optionsUrl)]
+    pub fn options_url(this: &ExtensionInfo) -> String;
+
+    // Whether the extension declares support for offline use (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = offlineEnabled)]
+    pub fn offline_enabled(this: &ExtensionInfo) -> Option<bool>;
+
+    #[wasm_bindgen(method, getter, js_name = disabledReason)]
+    fn disabled_reason_js(this: &ExtensionInfo) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = installType)]
+    fn install_type_js(this: &ExtensionInfo) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = type)]
+    fn type_js(this: &ExtensionInfo) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = icons)]
+    fn icons_js(this: &ExtensionInfo) -> Option<Array>;
+
+    #[wasm_bindgen(method, getter, js_name = permissions)]
+    fn permissions_js(this: &ExtensionInfo) -> Option<Array>;
+
+    #[wasm_bindgen(method, getter, js_name = hostPermissions)]
+    fn host_permissions_js(this: &ExtensionInfo) -> Option<Array>;
+}
+
+fn to_strings(values: Option<Array>) -> Vec<String> {
+    values
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl ExtensionInfo {
+    /// Why the extension is disabled, if it is.
+    pub fn disabled_reason(&self) -> Option<ExtensionDisabledReason> {
+        self.disabled_reason_js().map(ExtensionDisabledReason::from)
+    }
+
+    /// How the extension was installed.
+    pub fn install_type(&self) -> ExtensionInstallType {
+        ExtensionInstallType::from(self.install_type_js())
+    }
+
+    /// Whether the extension was loaded unpacked in developer mode.
+    pub fn is_development(&self) -> bool {
+        self.install_type() == ExtensionInstallType::Development
+    }
+
+    /// The type of the add-on.
+    pub fn type_(&self) -> ExtensionType {
+        ExtensionType::from(self.type_js())
+    }
+
+    /// The icons of the extension.
+    pub fn icons(&self) -> Vec<IconInfo> {
+        self.icons_js()
+            .map(|icons| icons.iter().map(JsCast::unchecked_into).collect())
+            .unwrap_or_default()
+    }
+
+    /// The API permissions of the extension.
+    pub fn permissions(&self) -> Vec<String> {
+        to_strings(self.permissions_js())
+    }
+
+    /// The host permissions of the extension.
+    pub fn host_permissions(&self) -> Vec<String> {
+        to_strings(self.host_permissions_js())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/management/#method-uninstallSelf
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type UninstallOptions;
+
+    #[wasm_bindgen(method, setter = showConfirmDialog)]
+    fn set_show_confirm_dialog(this: &UninstallOptions, value: bool);
+}
+
+impl UninstallOptions {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether to ask the user to confirm the uninstall. Defaults to `false`.
+    pub fn show_confirm_dialog(&mut self, value: bool) -> &mut Self {
+        self.set_show_confirm_dialog(value);
+        self
+    }
+}
+
+impl Default for UninstallOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/management/
+    pub type Management;
+
+    // Returns information about the calling extension.
+    #[wasm_bindgen(catch, method, js_name = getSelf)]
+    pub async fn get_self(this: &Management) -> Result<ExtensionInfo, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAll)]
+    async fn get_all_js(this: &Management) -> Result<Array, JsValue>;
+
+    // Returns information about the installed extension with the given ID.
+    #[wasm_bindgen(catch, method)]
+    pub async fn get(this: &Management, id: &str) -> Result<ExtensionInfo, JsValue>;
+
+    // Enables or disables the extension with the given ID.
+    #[wasm_bindgen(catch, method, js_name = setEnabled)]
+    pub async fn set_enabled(this: &Management, id: &str, enabled: bool) -> Result<(), JsValue>;
+
+    // Uninstalls the calling extension.
+    #[wasm_bindgen(catch, method, js_name = uninstallSelf)]
+    pub async fn uninstall_self(
+        this: &Management,
+        options: &UninstallOptions,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getPermissionWarningsByManifest)]
+    async fn get_permission_warnings_by_manifest_js(
+        this: &Management,
+        manifest: &str,
+    ) -> Result<Array, JsValue>;
+
+    // Fired when an add-on is installed. The listener receives the `ExtensionInfo`.
+    #[wasm_bindgen(method, getter, js_name = onInstalled)]
+    pub fn on_installed(this: &Management) -> EventTarget;
+
+    // Fired when an add-on is enabled. The listener receives the `ExtensionInfo`.
+    #[wasm_bindgen(method, getter, js_name = onEnabled)]
+    pub fn on_enabled(this: &Management) -> EventTarget;
+
+    // Fired when an add-on is disabled. The listener receives the `ExtensionInfo`.
+    #[wasm_bindgen(method, getter, js_name = onDisabled)]
+    pub fn on_disabled(this: &Management) -> EventTarget;
+
+    // Fired when an add-on is uninstalled. The listener receives the ID of the add-on.
+    #[wasm_bindgen(method, getter, js_name = onUninstalled)]
+    pub fn on_uninstalled(this: &Management) -> EventTarget;
+}
+
+impl Management {
+    /// Returns information about all installed extensions, apps and themes.
+    pub async fn get_all(&self) -> Result<Vec<ExtensionInfo>, JsValue> {
+        let extensions = self.get_all_js().await?;
+        Ok(extensions.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    /// Returns the permission warnings the given manifest would trigger,
+    /// e.g. to preview the prompt of an update (Chromium only).
+    pub async fn get_permission_warnings_by_manifest(
+        &self,
+        manifest: &str,
+    ) -> Result<Vec<String>, JsValue> {
+        let warnings = self
+            .get_permission_warnings_by_manifest_js(manifest)
+            .await?;
+        Ok(warnings.iter().filter_map(|w| w.as_string()).collect())
+    }
+}
@@ -0,0 +1,578 @@
+//! Bindings to the `notifications` API.
+
+use crate::{EventTarget, ListenerHandle};
+use js_sys::{Array, Object};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/#type-TemplateType
+    /// The layout of a notification.
+    TemplateType {
+        /// Icon, title, message, expanded message and up to two buttons.
+        Basic = "basic",
+        /// Icon, title, message, expanded message, image and up to two buttons.
+        Image = "image",
+        /// Icon, title, message, items and up to two buttons.
+        List = "list",
+        /// Icon, title, message, progress and up to two buttons.
+        Progress = "progress",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/#type-PermissionLevel
+    /// Whether the user allows notifications from the extension.
+    PermissionLevel {
+        Granted = "granted",
+        Denied = "denied",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/#type-NotificationOptions
+    /// An action button of a notification.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type NotificationButton;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &NotificationButton, value: &str);
+
+    #[wasm_bindgen(method, setter = iconUrl)]
+    fn set_icon_url(this: &NotificationButton, value: &str);
+}
+
+impl NotificationButton {
+    pub fn new(title: &str) -> Self {
+        let button: Self = Object::new().unchecked_into();
+        button.set_title(title);
+        button
+    }
+
+    /// An icon shown next to the title of the button.
+    pub fn icon_url(&mut self, value: &str) -> &mut Self {
+        self.set_icon_url(value);
+        self
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/#type-NotificationItem
+    /// An entry of a list notification.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type NotificationItem;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &NotificationItem, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_message(this: &NotificationItem, value: &str);
+}
+
+impl NotificationItem {
+    pub fn new(title: &str, message: &str) -> Self {
+        let item: Self = Object::new().unchecked_into();
+        item.set_title(title);
+        item.set_message(message);
+        item
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/#type-NotificationOptions
+    /// The contents of a notification.
+    ///
+    /// [`Notifications::create`] requires the template type, icon, title and
+    /// message, which the template constructors like [`NotificationOptions::new_basic`] set.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type NotificationOptions;
+
+    #[wasm_bindgen(method, setter = type)]
+    fn set_template_type(this: &NotificationOptions, value: JsValue);
+
+    #[wasm_bindgen(method, setter = iconUrl)]
+    fn set_icon_url(this: &NotificationOptions, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &NotificationOptions, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_message(this: &NotificationOptions, value: &str);
+
+    #[wasm_bindgen(method, setter = contextMessage)]
+    fn set_context_message(this: &NotificationOptions, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_priority(this: &NotificationOptions, value: i32);
+
+    #[wasm_bindgen(method, setter = eventTime)]
+    fn set_event_time(this: &NotificationOptions, value: f64);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_buttons(this: &NotificationOptions, value: &Array);
+
+    #[wasm_bindgen(method, setter = imageUrl)]
+    fn set_image_url(this: &NotificationOptions, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_items(this: &NotificationOptions, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_progress(this: &NotificationOptions, value: u32);
+
+    #[wasm_bindgen(method, setter = requireInteraction)]
+    fn set_require_interaction(this: &NotificationOptions, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_silent(this: &NotificationOptions, value: bool);
+}
+
+impl NotificationOptions {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Which type of notification to display.
+    pub fn template_type(&mut self, value: TemplateType) -> &mut Self {
+        self.set_template_type(value.into());
+        self
+    }
+
+    /// A URL to the sender's avatar, app icon, or a thumbnail for image notifications.
+    pub fn icon_url(&mut self, value: &str) -> &mut Self {
+        self.set_icon_url(value);
+        self
+    }
+
+    /// Title of the notification.
+    pub fn title(&mut self, value: &str) -> &mut Self {
+        self.set_title(value);
+        self
+    }
+
+    /// Main notification content.
+    pub fn message(&mut self, value: &str) -> &mut Self {
+        self.set_message(value);
+        self
+    }
+
+    /// Alternate notification content with a lower-weight font.
+    pub fn context_message(&mut self, value: &str) -> &mut Self {
+        self.set_context_message(value);
+        self
+    }
+
+    /// Priority ranges from -2 to 2, zero is the default.
+    pub fn priority(&mut self, value: i32) -> &mut Self {
+        self.set_priority(value);
+        self
+    }
+
+    /// A timestamp associated with the notification, in milliseconds past the epoch.
+    pub fn event_time(&mut self, value: f64) -> &mut Self {
+        self.set_event_time(value);
+        self
+    }
+
+    /// Up to two notification action buttons.
+    pub fn buttons(&mut self, value: &[NotificationButton]) -> &mut Self {
+        self.set_buttons(&value.iter().collect());
+        self
+    }
+
+    /// A URL to the image of an image notification.
+    pub fn image_url(&mut self, value: &str) -> &mut Self {
+        self.set_image_url(value);
+        self
+    }
+
+    /// Items for list notifications.
+    pub fn items(&mut self, value: &[NotificationItem]) -> &mut Self {
+        self.set_items(&value.iter().collect());
+        self
+    }
+
+    /// Current progress of a progress notification, from 0 to 100.
+    pub fn progress(&mut self, value: u32) -> &mut Self {
+        self.set_progress(value);
+        self
+    }
+
+    /// Whether the notification stays visible until the user dismisses or activates it.
+    pub fn require_interaction(&mut self, value: bool) -> &mut Self {
+        self.set_require_interaction(value);
+        self
+    }
+
+    /// Whether no sounds or vibrations are made when the notification appears.
+    pub fn silent(&mut self, value: bool) -> &mut Self {
+        self.set_silent(value);
+        self
+    }
+}
+
+impl Default for NotificationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationOptions {
+    fn template(template_type: TemplateType, icon_url: &str, title: &str, message: &str) -> Self {
+        let mut options = Self::new();
+        options
+            .template_type(template_type)
+            .icon_url(icon_url)
+            .title(title)
+            .message(message);
+        options
+    }
+
+    /// A notification with an icon, a title and a message.
+    pub fn new_basic(icon_url: &str, title: &str, message: &str) -> Self {
+        Self::template(TemplateType::Basic, icon_url, title, message)
+    }
+
+    /// A basic notification that also shows an image.
+    pub fn new_image(icon_url: &str, title: &str, message: &str, image_url: &str) -> Self {
+        let mut options = Self::template(TemplateType::Image, icon_url, title, message);
+        options.image_url(image_url);
+        options
+    }
+
+    /// A basic notification that also shows a list of items.
+    pub fn new_list(
+        icon_url: &str,
+        title: &str,
+        message: &str,
+        items: &[NotificationItem],
+    ) -> Self {
+        let mut options = Self::template(TemplateType::List, icon_url, title, message);
+        options.items(items);
+        options
+    }
+
+    /// A basic notification that also shows a progress bar, from 0 to 100.
+    pub fn new_progress(icon_url: &str, title: &str, message: &str, progress: u32) -> Self {
+        let mut options = Self::template(TemplateType::Progress, icon_url, title, message);
+        options.progress(progress);
+        options
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/notifications/
+    #[derive(Debug, Clone)]
+    pub type Notifications;
+
+    // Creates and displays a notification and resolves to its ID.
+    #[wasm_bindgen(catch, method, js_name = create)]
+    async fn create_js(
+        this: &Notifications,
+        notification_id: Option<&str>,
+        options: &NotificationOptions,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = update)]
+    async fn update_js(
+        this: &Notifications,
+        notification_id: &str,
+        options: &NotificationOptions,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = clear)]
+    async fn clear_js(this: &Notifications, notification_id: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAll)]
+    async fn get_all_js(this: &Notifications) -> Result<Object, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getPermissionLevel)]
+    async fn get_permission_level_js(this: &Notifications) -> Result<JsValue, JsValue>;
+
+    // The user clicked in a non-button area of the notification. The listener receives its ID.
+    #[wasm_bindgen(method, getter, js_name = onClicked)]
+    pub fn on_clicked(this: &Notifications) -> EventTarget;
+
+    // The user pressed a button in the notification. The listener receives its ID and the button index.
+    #[wasm_bindgen(method, getter, js_name = onButtonClicked)]
+    pub fn on_button_clicked(this: &Notifications) -> EventTarget;
+
+    // The notification closed. The listener receives its ID and whether the user closed it.
+    #[wasm_bindgen(method, getter, js_name = onClosed)]
+    pub fn on_closed(this: &Notifications) -> EventTarget;
+
+    // The user clicked on a link for the app's notification settings.
+    #[wasm_bindgen(method, getter, js_name = onShowSettings)]
+    pub fn on_show_settings(this: &Notifications) -> EventTarget;
+}
+
+impl Notifications {
+    /// Creates and displays a notification and returns its ID.
+    ///
+    /// Without an ID one is generated; an existing notification with the
+    /// same ID is cleared first.
+    pub async fn create(
+        &self,
+        notification_id: Option<&str>,
+        options: &NotificationOptions,
+    ) -> Result<String, JsValue> {
+        let id = self.create_js(notification_id, options).await?;
+        id.as_string()
+            .ok_or_else(|| js_sys::Error::new("notifications.create did not return an ID").into())
+    }
+
+    /// Updates an existing notification and returns whether it existed.
+    pub async fn update(
+        &self,
+        notification_id: &str,
+        options: &NotificationOptions,
+    ) -> Result<bool, JsValue> {
+        Ok(self.update_js(notification_id, options).await?.is_truthy())
+    }
+
+    /// Clears a notification and returns whether it existed.
+    pub async fn clear(&self, notification_id: &str) -> Result<bool, JsValue> {
+        Ok(self.clear_js(notification_id).await?.is_truthy())
+    }
+
+    /// Returns the IDs of all notifications of the extension.
+    pub async fn get_all(&self) -> Result<Vec<String>, JsValue> {
+        let notifications = self.get_all_js().await?;
+        Ok(Object::keys(&notifications)
+            .iter()
+            .filter_map(|id| id.as_string())
+            .collect())
+    }
+
+    /// Whether the user allows notifications from the extension.
+    pub async fn get_permission_level(&self) -> Result<PermissionLevel, JsValue> {
+        let level = self.get_permission_level_js().await?;
+        Ok(PermissionLevel::from(level.as_string().unwrap_or_default()))
+    }
+}
+
+/// Callbacks for the events of a single notification.
+#[derive(Default)]
+pub struct NotificationCallbacks {
+    on_clicked: Option<Box<dyn Fn()>>,
+    on_button_clicked: Option<Box<dyn Fn(u32)>>,
+    on_closed: Option<Box<dyn Fn(bool)>>,
+}
+
+impl NotificationCallbacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called when the user clicks the notification outside of its buttons.
+    pub fn on_clicked(mut self, callback: impl Fn() + 'static) -> Self {
+        self.on_clicked = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with the index of the button the user pressed.
+    pub fn on_button_clicked(mut self, callback: impl Fn(u32) + 'static) -> Self {
+        self.on_button_clicked = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with whether the user closed the notification.
+    pub fn on_closed(mut self, callback: impl Fn(bool) + 'static) -> Self {
+        self.on_closed = Some(Box::new(callback));
+        self
+    }
+}
+
+impl fmt::Debug for NotificationCallbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationCallbacks")
+            .field("on_clicked", &self.on_clicked.is_some())
+            .field("on_button_clicked", &self.on_button_clicked.is_some())
+            .field("on_closed", &self.on_closed.is_some())
+            .finish()
+    }
+}
+
+type Callbacks = Rc<RefCell<HashMap<String, Rc<NotificationCallbacks>>>>;
+
+/// Routes the events of notifications to the callbacks they were created with.
+///
+/// The callbacks live in memory, so notifications that outlive a suspended
+/// service worker lose them.
+#[derive(Debug)]
+pub struct NotificationRegistry {
+    notifications: Notifications,
+    callbacks: Callbacks,
+    created: Cell<u64>,
+    _on_clicked: ListenerHandle<dyn FnMut(String)>,
+    _on_button_clicked: ListenerHandle<dyn FnMut(String, u32)>,
+    _on_closed: ListenerHandle<dyn FnMut(String, bool)>,
+}
+
+impl NotificationRegistry {
+    /// Registers the listeners for `onClicked`, `onButtonClicked` and `onClosed`.
+    ///
+    /// They are removed again when the registry is dropped.
+    pub fn new(notifications: &Notifications) -> Self {
+        let callbacks = Callbacks::default();
+        let lookup = |callbacks: &Callbacks, id: &str| callbacks.borrow().get(id).cloned();
+        let on_clicked = {
+            let callbacks = Rc::clone(&callbacks);
+            Closure::<dyn FnMut(String)>::new(move |id: String| {
+                if let Some(on_clicked) = lookup(&callbacks, &id)
+                    .as_deref()
+                    .and_then(|c| c.on_clicked.as_ref())
+                {
+                    on_clicked();
+                }
+            })
+        };
+        let on_button_clicked = {
+            let callbacks = Rc::clone(&callbacks);
+            Closure::<dyn FnMut(String, u32)>::new(move |id: String, index: u32| {
+                if let Some(on_button_clicked) = lookup(&callbacks, &id)
+                    .as_deref()
+                    .and_then(|c| c.on_button_clicked.as_ref())
+                {
+                    on_button_clicked(index);
+                }
+            })
+        };
+        let on_closed = {
+            let callbacks = Rc::clone(&callbacks);
+            Closure::<dyn FnMut(String, bool)>::new(move |id: String, by_user: bool| {
+                let removed = callbacks.borrow_mut().remove(&id);
+                if let Some(on_closed) = removed.as_deref().and_then(|c| c.on_closed.as_ref()) {
+                    on_closed(by_user);
+                }
+            })
+        };
+        Self {
+            notifications: notifications.clone(),
+            callbacks,
+            created: Cell::new(0),
+            _on_clicked: notifications.on_clicked().listen(on_clicked),
+            _on_button_clicked: notifications.on_button_clicked().listen(on_button_clicked),
+            _on_closed: notifications.on_closed().listen(on_closed),
+        }
+    }
+
+    /// Creates a notification and routes its events to `callbacks`.
+    ///
+    /// Without an ID the registry generates one, so that the callbacks are
+    /// in place before the browser can fire the first event.
+    pub async fn create(
+        &self,
+        notification_id: Option<&str>,
+        options: &NotificationOptions,
+        callbacks: NotificationCallbacks,
+    ) -> Result<String, JsValue> {
+        let id = match notification_id {
+            Some(id) => id.to_owned(),
+            None => self.generate_id(),
+        };
+        let callbacks = Rc::new(callbacks);
+        self.callbacks
+            .borrow_mut()
+            .insert(id.clone(), Rc::clone(&callbacks));
+        let created = self.notifications.create(Some(&id), options).await;
+        let mut registered = self.callbacks.borrow_mut();
+        // The entry may have been replaced by another `create` in the meantime.
+        let ours = registered
+            .get(&id)
+            .is_some_and(|entry| Rc::ptr_eq(entry, &callbacks));
+        if ours && created.as_ref() != Ok(&id) {
+            registered.remove(&id);
+            if let Ok(created) = &created {
+                registered.insert(created.clone(), callbacks);
+            }
+        }
+        created
+    }
+
+    /// A notification ID that is unique across restarts of the extension.
+    fn generate_id(&self) -> String {
+        let count = self.created.get();
+        self.created.set(count + 1);
+        format!("{}-{count}", js_sys::Date::now())
+    }
+
+    /// Clears a notification and forgets its callbacks.
+    pub async fn clear(&self, notification_id: &str) -> Result<bool, JsValue> {
+        self.callbacks.borrow_mut().remove(notification_id);
+        self.notifications.clear(notification_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Run with `wasm-pack test --node` or `cargo test --target wasm32-unknown-unknown`.
+    #[cfg(target_arch = "wasm32")]
+    mod wasm {
+        use super::super::*;
+        use js_sys::Function;
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        /// A JS event object with `addListener` and `removeListener`.
+        const EVENT: &str = "{ listeners: [], addListener(l) { this.listeners.push(l) }, \
+             removeListener(l) { this.listeners.splice(this.listeners.indexOf(l), 1) } }";
+
+        /// A `notifications` namespace that fires `onClicked` before `create`
+        /// resolves, and fails to create the notification with the id `fail`.
+        fn notifications() -> Notifications {
+            Function::new_no_args(&format!(
+                r#"
+                const onClicked = {event};
+                return {{
+                    onClicked,
+                    onButtonClicked: {event},
+                    onClosed: {event},
+                    create: async (id) => {{
+                        if (id === "fail") throw new Error("invalid options");
+                        onClicked.listeners.forEach((listener) => listener(id));
+                        return id;
+                    }},
+                }};
+                "#,
+                event = EVENT
+            ))
+            .call0(&JsValue::NULL)
+            .unwrap()
+            .unchecked_into()
+        }
+
+        #[wasm_bindgen_test]
+        async fn callbacks_receive_events_fired_during_create() {
+            let notifications = notifications();
+            let registry = NotificationRegistry::new(&notifications);
+            let clicks = Rc::new(Cell::new(0));
+            let callbacks = {
+                let clicks = Rc::clone(&clicks);
+                NotificationCallbacks::new().on_clicked(move || clicks.set(clicks.get() + 1))
+            };
+            let options = NotificationOptions::new();
+            let id = registry.create(None, &options, callbacks).await.unwrap();
+            assert!(!id.is_empty());
+            assert_eq!(clicks.get(), 1);
+            assert!(registry.callbacks.borrow().contains_key(&id));
+
+            let failed = registry
+                .create(Some("fail"), &options, NotificationCallbacks::new())
+                .await;
+            assert!(failed.is_err());
+            assert!(!registry.callbacks.borrow().contains_key("fail"));
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Bindings to the `offscreen` API.
+
+use crate::{
+    json::{from_js, to_js},
+    ListenerHandle, Runtime,
+};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    future::Future,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, spawn_local, JsFuture};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/offscreen/#type-Reason
+    /// Why an offscreen document is needed.
+    OffscreenReason {
+        Testing = "TESTING",
+        AudioPlayback = "AUDIO_PLAYBACK",
+        IframeScripting = "IFRAME_SCRIPTING",
+        DomScraping = "DOM_SCRAPING",
+        Blobs = "BLOBS",
+        DomParser = "DOM_PARSER",
+        UserMedia = "USER_MEDIA",
+        DisplayMedia = "DISPLAY_MEDIA",
+        WebRtc = "WEB_RTC",
+        Clipboard = "CLIPBOARD",
+        LocalStorage = "LOCAL_STORAGE",
+        Workers = "WORKERS",
+        BatteryStatus = "BATTERY_STATUS",
+        MatchMedia = "MATCH_MEDIA",
+        Geolocation = "GEOLOCATION",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/offscreen/#type-CreateParameters
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type CreateParameters;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &CreateParameters, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_reasons(this: &CreateParameters, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_justification(this: &CreateParameters, value: &str);
+}
+
+impl CreateParameters {
+    /// The document at `url`, a page of the extension, is created for `reasons`.
+    /// `justification` explains to the user why it is needed.
+    pub fn new(url: &str, reasons: &[OffscreenReason], justification: &str) -> Self {
+        let parameters: Self = Object::new().unchecked_into();
+        parameters.set_url(url);
+        parameters.set_reasons(&reasons.iter().cloned().map(JsValue::from).collect());
+        parameters.set_justification(justification);
+        parameters
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/offscreen/
+    #[derive(Debug, Clone)]
+    pub type Offscreen;
+
+    // Creates the offscreen document. An extension can only have one at a time.
+    #[wasm_bindgen(catch, method, js_name = createDocument)]
+    pub async fn create_document(
+        this: &Offscreen,
+        parameters: &CreateParameters,
+    ) -> Result<(), JsValue>;
+
+    // Closes the open offscreen document.
+    #[wasm_bindgen(catch, method, js_name = closeDocument)]
+    pub async fn close_document(this: &Offscreen) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = hasDocument)]
+    async fn has_document_js(this: &Offscreen) -> Result<JsValue, JsValue>;
+}
+
+impl Offscreen {
+    /// Whether the extension has an open offscreen document.
+    pub async fn has_document(&self) -> Result<bool, JsValue> {
+        Ok(self.has_document_js().await?.is_truthy())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &JsValue, timeout: i32) -> JsValue;
+
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(id: &JsValue);
+}
+
+/// The message sent into the document. `target` keeps other extension
+/// pages from answering it.
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    target: &'a str,
+    data: &'a T,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Reply<T> {
+    Ok(T),
+    Err(String),
+}
+
+/// The listener type of [`OffscreenDocument::serve`].
+pub type OffscreenMessageListener = dyn FnMut(JsValue, JsValue, Function) -> bool;
+
+struct DocumentState {
+    offscreen: Offscreen,
+    runtime: Runtime,
+    parameters: CreateParameters,
+    target: RefCell<String>,
+    idle_timeout: Cell<Option<Duration>>,
+    /// The last create or close operation; the next one waits for it.
+    lifecycle: RefCell<Option<Promise>>,
+    pending: Cell<usize>,
+    timer: RefCell<Option<JsValue>>,
+}
+
+impl DocumentState {
+    /// Runs `operation` after all earlier create and close operations, so
+    /// concurrent callers never see a half-created or half-closed document.
+    fn enqueue(
+        &self,
+        operation: impl Future<Output = Result<(), JsValue>> + 'static,
+    ) -> impl Future<Output = Result<(), JsValue>> {
+        let previous = self.lifecycle.borrow_mut().take();
+        let promise = future_to_promise(async move {
+            if let Some(previous) = previous {
+                // A failure of the earlier operation is reported to its own caller.
+                let _ = JsFuture::from(previous).await;
+            }
+            operation.await.map(|()| JsValue::UNDEFINED)
+        });
+        *self.lifecycle.borrow_mut() = Some(promise.clone());
+        let result = JsFuture::from(promise);
+        async move { result.await.map(drop) }
+    }
+
+    fn ensure(&self) -> impl Future<Output = Result<(), JsValue>> {
+        let offscreen = self.offscreen.clone();
+        let parameters = self.parameters.clone();
+        // The document may also be left over from an earlier service worker.
+        self.enqueue(async move {
+            if !offscreen.has_document().await? {
+                offscreen.create_document(&parameters).await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn close(&self) -> impl Future<Output = Result<(), JsValue>> {
+        self.cancel_timer();
+        let offscreen = self.offscreen.clone();
+        self.enqueue(async move {
+            if offscreen.has_document().await? {
+                offscreen.close_document().await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn cancel_timer(&self) {
+        if let Some(timer) = self.timer.borrow_mut().take() {
+            clear_timeout(&timer);
+        }
+    }
+
+    fn schedule_close(self: &Rc<Self>) {
+        let Some(timeout) = self.idle_timeout.get() else {
+            return;
+        };
+        let state = Rc::downgrade(self);
+        let handler = Closure::once_into_js(move || {
+            let Some(state) = Weak::upgrade(&state) else {
+                return;
+            };
+            state.timer.borrow_mut().take();
+            if state.pending.get() == 0 {
+                spawn_local(async move {
+                    let _ = state.close().await;
+                });
+            }
+        });
+        let millis = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        self.cancel_timer();
+        *self.timer.borrow_mut() = Some(set_timeout(&handler, millis));
+    }
+}
+
+impl Drop for DocumentState {
+    fn drop(&mut self) {
+        self.cancel_timer();
+    }
+}
+
+/// Holds off the idle timer while a request is in flight, even if the
+/// request future is dropped early.
+struct PendingRequest<'a>(&'a Rc<DocumentState>);
+
+impl<'a> PendingRequest<'a> {
+    fn new(state: &'a Rc<DocumentState>) -> Self {
+        state.pending.set(state.pending.get() + 1);
+        state.cancel_timer();
+        Self(state)
+    }
+}
+
+impl Drop for PendingRequest<'_> {
+    fn drop(&mut self) {
+        let pending = self.0.pending.get() - 1;
+        self.0.pending.set(pending);
+        if pending == 0 {
+            self.0.schedule_close();
+        }
+    }
+}
+
+/// Manages the offscreen document of the extension.
+///
+/// The document is created on first use and only once, even if several
+/// requests race for it, and can be closed automatically after an idle
+/// time. Requests are sent with `runtime.sendMessage`; the document answers
+/// them with [`OffscreenDocument::serve`].
+pub struct OffscreenDocument {
+    state: Rc<DocumentState>,
+}
+
+impl OffscreenDocument {
+    /// The default `target` of the messages sent into the document.
+    pub const DEFAULT_TARGET: &'static str = "offscreen";
+
+    pub fn new(offscreen: &Offscreen, runtime: &Runtime, parameters: CreateParameters) -> Self {
+        Self {
+            state: Rc::new(DocumentState {
+                offscreen: offscreen.clone(),
+                runtime: runtime.clone(),
+                parameters,
+                target: RefCell::new(Self::DEFAULT_TARGET.to_owned()),
+                idle_timeout: Cell::new(None),
+                lifecycle: RefCell::default(),
+                pending: Cell::new(0),
+                timer: RefCell::default(),
+            }),
+        }
+    }
+
+    /// Closes the document when no request was made for `timeout`.
+    ///
+    /// By default the document stays open.
+    pub fn idle_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.state.idle_timeout.set(timeout);
+        if timeout.is_none() {
+            self.state.cancel_timer();
+        }
+        self
+    }
+
+    /// The `target` the messages are tagged with, for extensions with more
+    /// than one kind of document listening to `runtime.onMessage`.
+    pub fn target(&mut self, target: &str) -> &mut Self {
+        *self.state.target.borrow_mut() = target.to_owned();
+        self
+    }
+
+    /// Creates the document unless it is already open.
+    pub async fn ensure(&self) -> Result<(), JsValue> {
+        self.state.ensure().await
+    }
+
+    /// Closes the document if it is open.
+    pub async fn close(&self) -> Result<(), JsValue> {
+        self.state.close().await
+    }
+
+    /// Sends `request` to the document, creating it if needed, and returns
+    /// its response.
+    ///
+    /// Fails if the document does not answer, or its handler fails.
+    pub async fn request<Req, Res>(&self, request: &Req) -> Result<Res, JsValue>
+    where
+        Req: Serialize,
+        Res: DeserializeOwned,
+    {
+        let _pending = PendingRequest::new(&self.state);
+        self.send(request).await
+    }
+
+    async fn send<Req, Res>(&self, request: &Req) -> Result<Res, JsValue>
+    where
+        Req: Serialize,
+        Res: DeserializeOwned,
+    {
+        self.state.ensure().await?;
+        let message = to_js(&Envelope {
+            target: &self.state.target.borrow(),
+            data: request,
+        })?;
+        let reply = self
+            .state
+            .runtime
+            .send_message(None, &message, None)
+            .await?;
+        if reply.is_undefined() || reply.is_null() {
+            return Err(js_sys::Error::new("the offscreen document did not respond").into());
+        }
+        match from_js(&reply)? {
+            Reply::Ok(response) => Ok(response),
+            Reply::Err(error) => Err(js_sys::Error::new(&error).into()),
+        }
+    }
+
+    /// Answers the requests of [`OffscreenDocument::request`] in the
+    /// offscreen document.
+    ///
+    /// Messages with another `target` are left to other listeners.
+    /// An `Err` returned by `handler` fails the request.
+    pub fn serve<Req, Res, F, Fut>(
+        runtime: &Runtime,
+        target: &str,
+        handler: F,
+    ) -> ListenerHandle<OffscreenMessageListener>
+    where
+        Req: DeserializeOwned,
+        Res: Serialize,
+        F: Fn(Req) -> Fut + 'static,
+        Fut: Future<Output = Result<Res, String>> + 'static,
+    {
+        let target = target.to_owned();
+        let listener = Closure::<OffscreenMessageListener>::new(
+            move |message: JsValue, _sender: JsValue, send_response: Function| {
+                let is_ours = Reflect::get(&message, &JsValue::from_str("target"))
+                    .ok()
+                    .and_then(|value| value.as_string())
+                    .is_some_and(|value| value == target);
+                if !is_ours {
+                    return false;
+                }
+                let request = Reflect::get(&message, &JsValue::from_str("data"))
+                    .and_then(|data| from_js::<Req>(&data));
+                let response = request.map(&handler);
+                spawn_local(async move {
+                    let reply = match response {
+                        Ok(response) => match response.await {
+                            Ok(value) => to_js(&Reply::Ok(value)),
+                            Err(error) => to_js(&Reply::<()>::Err(error)),
+                        },
+                        Err(error) => to_js(&Reply::<()>::Err(format!(
+                            "invalid offscreen request: {error:?}"
+                        ))),
+                    };
+                    if let Ok(reply) = reply {
+                        let _ = send_response.call1(&JsValue::NULL, &reply);
+                    }
+                });
+                // Keeps the channel open for the asynchronous response.
+                true
+            },
+        );
+        runtime.on_message().listen(listener)
+    }
+}
+
+impl fmt::Debug for OffscreenDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffscreenDocument")
+            .field("parameters", &self.state.parameters)
+            .field("target", &self.state.target.borrow())
+            .field("idle_timeout", &self.state.idle_timeout.get())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Run with `wasm-pack test --node` or `cargo test --target wasm32-unknown-unknown`.
+    #[cfg(target_arch = "wasm32")]
+    mod wasm {
+        use super::super::*;
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        /// A `runtime` namespace whose `send` method passes a message to the
+        /// `onMessage` listeners and resolves with the response.
+        fn runtime() -> Runtime {
+            Function::new_no_args(
+                r#"
+                const listeners = [];
+                return {
+                    onMessage: {
+                        addListener: (listener) => listeners.push(listener),
+                        removeListener: (listener) => listeners.splice(listeners.indexOf(listener), 1),
+                    },
+                    send: (message) => new Promise((resolve) => {
+                        listeners.forEach((listener) => listener(message, {}, resolve));
+                    }),
+                };
+                "#,
+            )
+            .call0(&JsValue::NULL)
+            .unwrap()
+            .unchecked_into()
+        }
+
+        async fn send(runtime: &Runtime, message: &str) -> Reply<u32> {
+            let send: Function = Reflect::get(runtime, &"send".into())
+                .unwrap()
+                .unchecked_into();
+            let message = js_sys::JSON::parse(message).unwrap();
+            let promise: Promise = send.call1(runtime, &message).unwrap().unchecked_into();
+            from_js(&JsFuture::from(promise).await.unwrap()).unwrap()
+        }
+
+        #[wasm_bindgen_test]
+        async fn serve_answers_invalid_requests() {
+            let runtime = runtime();
+            let _handle = OffscreenDocument::serve(&runtime, "worker", |n: u32| async move {
+                Ok::<_, String>(n * 2)
+            });
+            assert!(matches!(
+                send(&runtime, r#"{ "target": "worker", "data": 21 }"#).await,
+                Reply::Ok(42)
+            ));
+            assert!(matches!(
+                send(&runtime, r#"{ "target": "worker" }"#).await,
+                Reply::Err(error) if error.starts_with("invalid offscreen request")
+            ));
+            assert!(matches!(
+                send(&runtime, r#"{ "target": "worker", "data": "a" }"#).await,
+                Reply::Err(error) if error.starts_with("invalid offscreen request")
+            ));
+        }
+    }
+}
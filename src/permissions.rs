@@ -0,0 +1,306 @@
+//! Bindings to the `permissions` API.
+
+use crate::{EventTarget, ListenerHandle};
+use js_sys::{Array, Object, Promise};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    future::Future,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/permissions/#type-Permissions
+    /// A set of API permissions and host permissions.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type Permissions;
+
+    #[wasm_bindgen(method, getter, js_name = permissions)]
+    fn permissions_js(this: &Permissions) -> Option<Array>;
+
+    #[wasm_bindgen(method, getter, js_name = origins)]
+    fn origins_js(this: &Permissions) -> Option<Array>;
+
+    #[wasm_bindgen(method, setter = permissions)]
+    fn set_permissions(this: &Permissions, value: &Array);
+
+    #[wasm_bindgen(method, setter = origins)]
+    fn set_origins(this: &Permissions, value: &Array);
+}
+
+fn strings<S: AsRef<str>>(values: &[S]) -> Array {
+    values
+        .iter()
+        .map(|value| JsValue::from_str(value.as_ref()))
+        .collect()
+}
+
+fn to_strings(values: Option<Array>) -> Vec<String> {
+    values
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Permissions {
+    /// API permissions like `"tabs"` and host permissions like `"https://*.example.com/*"`.
+    pub fn new<P: AsRef<str>, O: AsRef<str>>(permissions: &[P], origins: &[O]) -> Self {
+        let value: Self = Object::new().unchecked_into();
+        value.set_permissions(&strings(permissions));
+        value.set_origins(&strings(origins));
+        value
+    }
+
+    /// Only API permissions.
+    pub fn with_permissions<S: AsRef<str>>(permissions: &[S]) -> Self {
+        Self::new::<_, &str>(permissions, &[])
+    }
+
+    /// Only host permissions, as match patterns.
+    pub fn with_origins<S: AsRef<str>>(origins: &[S]) -> Self {
+        Self::new::<&str, _>(&[], origins)
+    }
+
+    /// The API permissions.
+    pub fn permissions(&self) -> Vec<String> {
+        to_strings(self.permissions_js())
+    }
+
+    /// The host permissions.
+    pub fn origins(&self) -> Vec<String> {
+        to_strings(self.origins_js())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/permissions/
+    #[derive(Debug, Clone)]
+    pub type BrowserPermissions;
+
+    #[wasm_bindgen(method, js_name = request)]
+    fn request_js(this: &BrowserPermissions, permissions: &Permissions) -> Promise;
+
+    #[wasm_bindgen(catch, method, js_name = contains)]
+    async fn contains_js(
+        this: &BrowserPermissions,
+        permissions: &Permissions,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = remove)]
+    async fn remove_js(
+        this: &BrowserPermissions,
+        permissions: &Permissions,
+    ) -> Result<JsValue, JsValue>;
+
+    // Gets the extension's current set of permissions.
+    #[wasm_bindgen(catch, method, js_name = getAll)]
+    pub async fn get_all(this: &BrowserPermissions) -> Result<Permissions, JsValue>;
+
+    // Fired when the extension acquires new permissions. The listener receives `Permissions`.
+    #[wasm_bindgen(method, getter, js_name = onAdded)]
+    pub fn on_added(this: &BrowserPermissions) -> EventTarget;
+
+    // Fired when permissions are removed from the extension. The listener receives `Permissions`.
+    #[wasm_bindgen(method, getter, js_name = onRemoved)]
+    pub fn on_removed(this: &BrowserPermissions) -> EventTarget;
+}
+
+impl BrowserPermissions {
+    /// Requests optional permissions and returns whether they were granted.
+    ///
+    /// The browser only shows the prompt during a user gesture, so the
+    /// request is sent as soon as this is called, e.g. in a click handler,
+    /// and not when the future is first polled.
+    pub fn request(
+        &self,
+        permissions: &Permissions,
+    ) -> impl Future<Output = Result<bool, JsValue>> {
+        let promise = JsFuture::from(self.request_js(permissions));
+        async move { Ok(promise.await?.is_truthy()) }
+    }
+
+    /// Whether the extension has all of the given permissions.
+    pub async fn contains(&self, permissions: &Permissions) -> Result<bool, JsValue> {
+        Ok(self.contains_js(permissions).await?.is_truthy())
+    }
+
+    /// Removes optional permissions and returns whether they were removed.
+    pub async fn remove(&self, permissions: &Permissions) -> Result<bool, JsValue> {
+        Ok(self.remove_js(permissions).await?.is_truthy())
+    }
+}
+
+type ChangeListener = dyn FnMut(Permissions);
+type ChangeCallback = Rc<dyn Fn(bool)>;
+
+struct PermissionState {
+    granted: Cell<Option<bool>>,
+    on_change: RefCell<Vec<ChangeCallback>>,
+}
+
+impl PermissionState {
+    fn update(&self, granted: bool) {
+        if self.granted.replace(Some(granted)) != Some(granted) {
+            // Not borrowed while the callbacks run, so they can register
+            // callbacks themselves, which are called from the next change.
+            let callbacks = self.on_change.borrow().clone();
+            for callback in callbacks {
+                callback(granted);
+            }
+        }
+    }
+}
+
+/// A feature that depends on optional permissions.
+///
+/// The grant state is cached and kept up to date through `onAdded` and
+/// `onRemoved`, including changes the user makes in the browser settings.
+/// The listeners are removed when the value is dropped.
+pub struct OptionalPermission {
+    api: BrowserPermissions,
+    required: Permissions,
+    state: Rc<PermissionState>,
+    _on_added: ListenerHandle<ChangeListener>,
+    _on_removed: ListenerHandle<ChangeListener>,
+}
+
+impl OptionalPermission {
+    pub fn new(api: &BrowserPermissions, required: Permissions) -> Self {
+        let state = Rc::new(PermissionState {
+            granted: Cell::new(None),
+            on_change: RefCell::default(),
+        });
+        // The event payload only names the changed permissions, and a removed
+        // origin pattern may cover a required one, so ask the browser instead.
+        let refresh = || {
+            let api = api.clone();
+            let required = required.clone();
+            let state = Rc::clone(&state);
+            Closure::<ChangeListener>::new(move |_| {
+                let (api, required, state) = (api.clone(), required.clone(), Rc::clone(&state));
+                spawn_local(async move {
+                    if let Ok(granted) = api.contains(&required).await {
+                        state.update(granted);
+                    }
+                });
+            })
+        };
+        Self {
+            _on_added: api.on_added().listen(refresh()),
+            _on_removed: api.on_removed().listen(refresh()),
+            api: api.clone(),
+            required,
+            state,
+        }
+    }
+
+    /// The permissions the feature requires.
+    pub fn required(&self) -> &Permissions {
+        &self.required
+    }
+
+    /// The last known grant state, or `None` before it was first checked.
+    pub fn granted(&self) -> Option<bool> {
+        self.state.granted.get()
+    }
+
+    /// Asks the browser whether the permissions are granted.
+    pub async fn check(&self) -> Result<bool, JsValue> {
+        let granted = self.api.contains(&self.required).await?;
+        self.state.update(granted);
+        Ok(granted)
+    }
+
+    /// Requests the permissions from the user, unless they are known to be granted.
+    ///
+    /// Must be called during a user gesture; see [`BrowserPermissions::request`].
+    pub fn request(&self) -> impl Future<Output = Result<bool, JsValue>> {
+        let request = (self.granted() != Some(true)).then(|| self.api.request(&self.required));
+        let state = Rc::clone(&self.state);
+        async move {
+            let Some(request) = request else {
+                return Ok(true);
+            };
+            let granted = request.await?;
+            state.update(granted);
+            Ok(granted)
+        }
+    }
+
+    /// Gives the permissions up again.
+    pub async fn remove(&self) -> Result<bool, JsValue> {
+        let removed = self.api.remove(&self.required).await?;
+        if removed {
+            self.state.update(false);
+        }
+        Ok(removed)
+    }
+
+    /// Calls `callback` with the new state whenever the permissions are
+    /// granted or revoked.
+    pub fn on_change(&self, callback: impl Fn(bool) + 'static) {
+        self.state.on_change.borrow_mut().push(Rc::new(callback));
+    }
+
+    /// Runs `feature` if the permissions are granted and returns `None` otherwise,
+    /// so the caller can fall back to a degraded mode.
+    pub async fn run<T>(&self, feature: impl FnOnce() -> T) -> Result<Option<T>, JsValue> {
+        let granted = match self.granted() {
+            Some(granted) => granted,
+            None => self.check().await?,
+        };
+        Ok(granted.then(feature))
+    }
+}
+
+impl fmt::Debug for OptionalPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OptionalPermission")
+            .field("required", &self.required)
+            .field("granted", &self.granted())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callbacks_can_register_callbacks() {
+        let state = Rc::new(PermissionState {
+            granted: Cell::new(None),
+            on_change: RefCell::default(),
+        });
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let callback = {
+            let state = Rc::downgrade(&state);
+            let calls = Rc::clone(&calls);
+            move |granted| {
+                calls.borrow_mut().push(("first", granted));
+                let calls = Rc::clone(&calls);
+                let state = state.upgrade().unwrap();
+                state.on_change.borrow_mut().push(Rc::new(move |granted| {
+                    calls.borrow_mut().push(("nested", granted))
+                }));
+            }
+        };
+        state.on_change.borrow_mut().push(Rc::new(callback));
+        state.update(true);
+        state.update(true);
+        state.update(false);
+        assert_eq!(
+            *calls.borrow(),
+            [("first", true), ("first", false), ("nested", false)]
+        );
+    }
+}
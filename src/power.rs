@@ -0,0 +1,193 @@
+//! Bindings to the `power` API.
+
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/power/#type-Level
+    /// What to keep awake.
+    KeepAwakeLevel {
+        /// Keeps the system awake, but lets the screen dim or turn off.
+        System = "system",
+        /// Keeps the screen and the system awake.
+        Display = "display",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/power/
+    #[derive(Debug, Clone)]
+    pub type Power;
+
+    #[wasm_bindgen(method, js_name = requestKeepAwake)]
+    fn request_keep_awake_js(this: &Power, level: JsValue);
+
+    #[wasm_bindgen(method, js_name = releaseKeepAwake)]
+    fn release_keep_awake_js(this: &Power);
+}
+
+/// The live `KeepAwake` guards of the thread, oldest first.
+#[derive(Debug, Default)]
+struct Guards {
+    next_id: u64,
+    levels: Vec<(u64, KeepAwakeLevel)>,
+}
+
+/// What to tell the browser after the live guards changed.
+#[derive(Debug, PartialEq)]
+enum Change {
+    Request(KeepAwakeLevel),
+    Release,
+}
+
+impl Guards {
+    const fn new() -> Self {
+        Self {
+            next_id: 0,
+            levels: Vec::new(),
+        }
+    }
+
+    /// The level to request for the live guards: `Display` if any guard
+    /// wants it, otherwise the level of the newest guard.
+    fn strongest(&self) -> Option<&KeepAwakeLevel> {
+        let mut levels = self.levels.iter().map(|(_, level)| level);
+        levels
+            .clone()
+            .find(|level| **level == KeepAwakeLevel::Display)
+            .or_else(|| levels.next_back())
+    }
+
+    /// Applies `update` and returns the change of the strongest level, if any.
+    fn update<R>(&mut self, update: impl FnOnce(&mut Self) -> R) -> (R, Option<Change>) {
+        let previous = self.strongest().cloned();
+        let result = update(self);
+        let change = match (previous, self.strongest()) {
+            (previous, current) if previous.as_ref() == current => None,
+            (_, Some(current)) => Some(Change::Request(current.clone())),
+            (_, None) => Some(Change::Release),
+        };
+        (result, change)
+    }
+
+    fn add(&mut self, level: KeepAwakeLevel) -> (u64, Option<Change>) {
+        self.update(|guards| {
+            let id = guards.next_id;
+            guards.next_id += 1;
+            guards.levels.push((id, level));
+            id
+        })
+    }
+
+    /// Forgets the live guards, whose request was released.
+    fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Change> {
+        self.update(|guards| guards.levels.retain(|(guard, _)| *guard != id))
+            .1
+    }
+}
+
+thread_local! {
+    static KEEP_AWAKE_GUARDS: RefCell<Guards> = const { RefCell::new(Guards::new()) };
+}
+
+impl Power {
+    /// Keeps the system awake until the returned guard is dropped.
+    ///
+    /// The browser only keeps one request per extension, so while there are
+    /// several guards the strongest level applies, `Display` over `System`.
+    /// Dropping a guard requests the strongest remaining level, and the
+    /// request is only released when the last guard is dropped.
+    pub fn keep_awake(&self, level: KeepAwakeLevel) -> KeepAwake {
+        let (id, change) = KEEP_AWAKE_GUARDS.with(|guards| guards.borrow_mut().add(level.clone()));
+        self.apply(change);
+        KeepAwake {
+            power: self.clone(),
+            id,
+            level,
+        }
+    }
+
+    /// Releases the request of the extension.
+    ///
+    /// The live [`KeepAwake`] guards no longer keep anything awake and do
+    /// nothing when they are dropped.
+    pub fn release_keep_awake(&self) {
+        KEEP_AWAKE_GUARDS.with(|guards| guards.borrow_mut().clear());
+        self.release_keep_awake_js();
+    }
+
+    fn apply(&self, change: Option<Change>) {
+        match change {
+            Some(Change::Request(level)) => self.request_keep_awake_js(level.into()),
+            Some(Change::Release) => self.release_keep_awake_js(),
+            None => {}
+        }
+    }
+}
+
+/// Keeps the system awake while it is alive; see [`Power::keep_awake`].
+#[derive(Debug)]
+#[must_use = "dropping a `KeepAwake` releases the request immediately"]
+pub struct KeepAwake {
+    power: Power,
+    id: u64,
+    level: KeepAwakeLevel,
+}
+
+impl KeepAwake {
+    /// The level this guard requested.
+    pub fn level(&self) -> &KeepAwakeLevel {
+        &self.level
+    }
+}
+
+impl Drop for KeepAwake {
+    fn drop(&mut self) {
+        let change = KEEP_AWAKE_GUARDS.with(|guards| guards.borrow_mut().remove(self.id));
+        self.power.apply(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use KeepAwakeLevel::*;
+
+    #[test]
+    fn display_is_the_strongest_level() {
+        let mut guards = Guards::new();
+        let (system, change) = guards.add(System);
+        assert_eq!(change, Some(Change::Request(System)));
+        let (display, change) = guards.add(Display);
+        assert_eq!(change, Some(Change::Request(Display)));
+        let (_, change) = guards.add(System);
+        assert_eq!(change, None);
+        assert_eq!(guards.remove(system), None);
+        assert_eq!(guards.remove(display), Some(Change::Request(System)));
+    }
+
+    #[test]
+    fn last_guard_releases() {
+        let mut guards = Guards::new();
+        let (first, _) = guards.add(System);
+        let (second, _) = guards.add(System);
+        assert_eq!(guards.remove(second), None);
+        assert_eq!(guards.remove(first), Some(Change::Release));
+    }
+
+    #[test]
+    fn guards_after_release_keep_awake() {
+        let mut guards = Guards::new();
+        let (old, _) = guards.add(System);
+        guards.clear();
+        let (new, change) = guards.add(System);
+        assert_eq!(change, Some(Change::Request(System)));
+        assert_eq!(guards.remove(old), None);
+        assert_eq!(guards.remove(new), Some(Change::Release));
+    }
+}
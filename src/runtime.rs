@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 extern "C" {
+    #[derive(Debug, Clone)]
     pub type Runtime;
 
     #[wasm_bindgen(catch, method, js_name = sendMessage)]
@@ -1,5 +1,5 @@
-use crate::tabs::Tab;
-use crate::windows::Window;
+use crate::tabs::{Tab, TabId};
+use crate::windows::{Window, WindowId};
 use crate::EventTarget;
 use js_sys::Object;
 use wasm_bindgen::prelude::*;
@@ -30,7 +30,7 @@ extern "C" {
     #[wasm_bindgen(catch, method, js_name = forgetClosedTab)]
     pub async fn forget_closed_tab(
         this: &Sessions,
-        window_id: i32,
+        window_id: WindowId,
         session_id: &str,
     ) -> Result<JsValue, JsValue>;
 
@@ -50,13 +50,16 @@ extern "C" {
     pub async fn restore(this: &Sessions, session_id: &str) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = getTabValue)]
-    pub async fn get_tab_value(this: &Sessions, tab_id: i32, key: &str)
-        -> Result<JsValue, JsValue>;
+    pub async fn get_tab_value(
+        this: &Sessions,
+        tab_id: TabId,
+        key: &str,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = setTabValue)]
     pub async fn set_tab_value(
         this: &Sessions,
-        tab_id: i32,
+        tab_id: TabId,
         key: &str,
         value: &JsValue,
     ) -> Result<JsValue, JsValue>;
@@ -64,21 +67,21 @@ extern "C" {
     #[wasm_bindgen(catch, method, js_name = removeTabValue)]
     pub async fn remove_tab_value(
         this: &Sessions,
-        tab_id: i32,
+        tab_id: TabId,
         key: &str,
     ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = getWindowValue)]
     pub async fn get_window_value(
         this: &Sessions,
-        window_id: i32,
+        window_id: WindowId,
         key: &str,
     ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = setWindowValue)]
     pub async fn set_window_value(
         this: &Sessions,
-        window_id: i32,
+        window_id: WindowId,
         key: &str,
         value: &JsValue,
     ) -> Result<JsValue, JsValue>;
@@ -86,7 +89,7 @@ extern "C" {
     #[wasm_bindgen(catch, method, js_name = removeWindowValue)]
     pub async fn remove_window_value(
         this: &Sessions,
-        window_id: i32,
+        window_id: WindowId,
         key: &str,
     ) -> Result<JsValue, JsValue>;
 
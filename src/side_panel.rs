@@ -0,0 +1,250 @@
+//! Bindings to the `sidePanel` API.
+
+use crate::{Browser, SidebarAction, TabId, WindowId};
+use js_sys::{Object, Promise, Reflect};
+use std::future::Future;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/sidePanel/#type-PanelOptions
+    /// The side panel shown globally or for a single tab.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type PanelOptions;
+
+    #[wasm_bindgen(method, getter, js_name = enabled)]
+    fn enabled_js(this: &PanelOptions) -> Option<bool>;
+
+    #[wasm_bindgen(method, getter, js_name = path)]
+    fn path_js(this: &PanelOptions) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = tabId)]
+    fn tab_id_js(this: &PanelOptions) -> Option<TabId>;
+
+    #[wasm_bindgen(method, setter = enabled)]
+    fn set_enabled(this: &PanelOptions, value: bool);
+
+    #[wasm_bindgen(method, setter = path)]
+    fn set_path(this: &PanelOptions, value: &str);
+
+    #[wasm_bindgen(method, setter = tabId)]
+    fn set_tab_id(this: &PanelOptions, value: TabId);
+}
+
+impl PanelOptions {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the side panel is available. Defaults to `true`.
+    pub fn enabled(&mut self, value: bool) -> &mut Self {
+        self.set_enabled(value);
+        self
+    }
+
+    /// The path of the page shown in the panel, relative to the extension.
+    pub fn path(&mut self, value: &str) -> &mut Self {
+        self.set_path(value);
+        self
+    }
+
+    /// Only applies the options to the given tab.
+    pub fn tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_tab_id(value);
+        self
+    }
+
+    /// Whether the side panel is available, if set.
+    pub fn get_enabled(&self) -> Option<bool> {
+        self.enabled_js()
+    }
+
+    /// The path of the page shown in the panel, if set.
+    pub fn get_path(&self) -> Option<String> {
+        self.path_js()
+    }
+
+    /// The tab the options apply to, or `None` for the global options.
+    pub fn get_tab_id(&self) -> Option<TabId> {
+        self.tab_id_js()
+    }
+}
+
+impl Default for PanelOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/sidePanel/#type-PanelBehavior
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type PanelBehavior;
+
+    // Whether clicking the extension's action icon opens the side panel.
+    #[wasm_bindgen(method, getter, js_name = openPanelOnActionClick)]
+    pub fn open_panel_on_action_click(this: &PanelBehavior) -> Option<bool>;
+
+    #[wasm_bindgen(method, setter = openPanelOnActionClick)]
+    fn set_open_panel_on_action_click(this: &PanelBehavior, value: bool);
+}
+
+impl PanelBehavior {
+    pub fn new(open_panel_on_action_click: bool) -> Self {
+        let behavior: Self = Object::new().unchecked_into();
+        behavior.set_open_panel_on_action_click(open_panel_on_action_click);
+        behavior
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/sidePanel/#type-OpenOptions
+    /// Where to open the side panel. Either a window or a tab is required.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type OpenOptions;
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &OpenOptions, value: WindowId);
+
+    #[wasm_bindgen(method, setter = tabId)]
+    fn set_tab_id(this: &OpenOptions, value: TabId);
+}
+
+impl OpenOptions {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Opens the global side panel of the window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+
+    /// Opens the panel of the tab, or the global panel if the tab has none.
+    pub fn tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_tab_id(value);
+        self
+    }
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/sidePanel/
+    #[derive(Debug, Clone)]
+    pub type SidePanel;
+
+    // Configures the side panel.
+    #[wasm_bindgen(catch, method, js_name = setOptions)]
+    pub async fn set_options(this: &SidePanel, options: &PanelOptions) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getOptions)]
+    async fn get_options_js(this: &SidePanel, options: &Object) -> Result<PanelOptions, JsValue>;
+
+    // Configures the behavior of the side panel.
+    #[wasm_bindgen(catch, method, js_name = setPanelBehavior)]
+    pub async fn set_panel_behavior(
+        this: &SidePanel,
+        behavior: &PanelBehavior,
+    ) -> Result<(), JsValue>;
+
+    // Returns the current behavior of the side panel.
+    #[wasm_bindgen(catch, method, js_name = getPanelBehavior)]
+    pub async fn get_panel_behavior(this: &SidePanel) -> Result<PanelBehavior, JsValue>;
+
+    #[wasm_bindgen(method, js_name = open)]
+    fn open_js(this: &SidePanel, options: &OpenOptions) -> Promise;
+}
+
+impl SidePanel {
+    /// Returns the options of the given tab, or the global options.
+    pub async fn get_options(&self, tab_id: Option<TabId>) -> Result<PanelOptions, JsValue> {
+        let options = Object::new();
+        if let Some(tab_id) = tab_id {
+            Reflect::set(&options, &JsValue::from_str("tabId"), &tab_id.into())?;
+        }
+        self.get_options_js(&options).await
+    }
+
+    /// Opens the side panel.
+    ///
+    /// Only works during a user gesture, so the call is made as soon as this
+    /// is called and not when the future is first polled.
+    pub fn open(&self, options: &OpenOptions) -> impl Future<Output = Result<(), JsValue>> {
+        let promise = JsFuture::from(self.open_js(options));
+        async move { promise.await.map(drop) }
+    }
+}
+
+/// The side UI of the running browser: `sidePanel` in Chromium and
+/// `sidebarAction` in Firefox.
+///
+/// Covers what both APIs can do; match on the variants for the rest.
+#[derive(Debug, Clone)]
+pub enum Sidebar {
+    SidePanel(SidePanel),
+    SidebarAction(SidebarAction),
+}
+
+impl Sidebar {
+    /// Picks the API the browser provides, preferring `sidePanel`.
+    ///
+    /// Returns `None` if the browser has neither, or the extension lacks
+    /// the `sidePanel` permission or `sidebar_action` manifest key.
+    pub fn detect(browser: &Browser) -> Option<Self> {
+        browser
+            .side_panel()
+            .map(Self::SidePanel)
+            .or_else(|| browser.sidebar_action().map(Self::SidebarAction))
+    }
+
+    /// Shows the page at `path` in the panel of the given tab, or of all
+    /// tabs without their own panel.
+    pub async fn set_panel(&self, path: &str, tab_id: Option<TabId>) -> Result<(), JsValue> {
+        match self {
+            Self::SidePanel(side_panel) => {
+                let mut options = PanelOptions::new();
+                options.path(path).enabled(true);
+                if let Some(tab_id) = tab_id {
+                    options.tab_id(tab_id);
+                }
+                side_panel.set_options(&options).await
+            }
+            Self::SidebarAction(sidebar_action) => {
+                let details = Object::new();
+                Reflect::set(&details, &JsValue::from_str("panel"), &path.into())?;
+                if let Some(tab_id) = tab_id {
+                    Reflect::set(&details, &JsValue::from_str("tabId"), &tab_id.into())?;
+                }
+                sidebar_action.set_panel(&details).await.map(drop)
+            }
+        }
+    }
+
+    /// Opens the panel in the given window.
+    ///
+    /// Only works during a user gesture; see [`SidePanel::open`]. Firefox
+    /// always opens the sidebar of the current window.
+    pub fn open(&self, window_id: WindowId) -> impl Future<Output = Result<(), JsValue>> {
+        let promise = match self {
+            Self::SidePanel(side_panel) => {
+                side_panel.open_js(OpenOptions::new().window_id(window_id))
+            }
+            Self::SidebarAction(sidebar_action) => sidebar_action.open_promise(),
+        };
+        let promise = JsFuture::from(promise);
+        async move { promise.await.map(drop) }
+    }
+}
@@ -1,13 +1,17 @@
-use js_sys::Object;
+use js_sys::{Object, Promise};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 extern "C" {
+    #[derive(Debug, Clone)]
     pub type SidebarAction;
 
     #[wasm_bindgen(catch, method)]
     pub async fn open(this: &SidebarAction) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(method, js_name = open)]
+    pub(crate) fn open_promise(this: &SidebarAction) -> Promise;
+
     #[wasm_bindgen(catch, method, js_name = setPanel)]
     pub async fn set_panel(this: &SidebarAction, details: &Object) -> Result<JsValue, JsValue>;
 }
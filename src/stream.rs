@@ -0,0 +1,317 @@
+//! [`Stream`] adapters for browser events.
+
+use crate::{EventTarget, ListenerHandle};
+use futures_core::Stream;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// A value an event passes to its listeners.
+///
+/// Implemented for [`JsCast`] types, which are cast without a runtime type
+/// check like the getters of this crate, and for [`String`], [`bool`],
+/// numbers, the id newtypes such as [`TabId`](crate::TabId), the string
+/// enums of this crate and [`Option`]s of these, which are converted and
+/// return `None` if the value has another type.
+///
+/// The marker `M` only tells the implementations for [`JsCast`] types apart
+/// from the others, and is inferred.
+pub trait EventArg<M>: Sized + 'static {
+    fn from_arg(value: JsValue) -> Option<Self>;
+}
+
+/// The marker of the [`EventArg`] implementation for [`JsCast`] types.
+#[derive(Debug)]
+pub enum CastArg {}
+
+/// The marker of the [`EventArg`] implementations that convert the value.
+#[derive(Debug)]
+pub enum ConvertArg {}
+
+impl<T: JsCast + 'static> EventArg<CastArg> for T {
+    fn from_arg(value: JsValue) -> Option<Self> {
+        Some(value.unchecked_into())
+    }
+}
+
+impl<M, T: EventArg<M>> EventArg<Option<M>> for Option<T> {
+    fn from_arg(value: JsValue) -> Option<Self> {
+        if value.is_undefined() || value.is_null() {
+            Some(None)
+        } else {
+            T::from_arg(value).map(Some)
+        }
+    }
+}
+
+impl EventArg<ConvertArg> for String {
+    fn from_arg(value: JsValue) -> Option<Self> {
+        value.as_string()
+    }
+}
+
+impl EventArg<ConvertArg> for bool {
+    fn from_arg(value: JsValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+macro_rules! number_event_arg {
+    ($($number:ty),*) => {
+        $(
+            impl EventArg<ConvertArg> for $number {
+                fn from_arg(value: JsValue) -> Option<Self> {
+                    value.as_f64().map(|number| number as $number)
+                }
+            }
+        )*
+    };
+}
+
+number_event_arg!(f64, f32, i32, u32, i64, u64);
+
+/// The arguments an event passes to its listeners.
+///
+/// Implemented for tuples of up to three [`EventArg`]s, e.g. `(Tab,)` for
+/// `tabs.onCreated`, `(TabId, TabChangeInfo, Tab)` for `tabs.onUpdated` or
+/// `(Object, String)` for `storage.onChanged`. Returns `None` if an
+/// argument can't be converted.
+pub trait EventArgs<M>: Sized + 'static {
+    fn from_args(first: JsValue, second: JsValue, third: JsValue) -> Option<Self>;
+}
+
+impl EventArgs<()> for () {
+    fn from_args(_: JsValue, _: JsValue, _: JsValue) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl<MA, A: EventArg<MA>> EventArgs<(MA,)> for (A,) {
+    fn from_args(first: JsValue, _: JsValue, _: JsValue) -> Option<Self> {
+        Some((A::from_arg(first)?,))
+    }
+}
+
+impl<MA, MB, A: EventArg<MA>, B: EventArg<MB>> EventArgs<(MA, MB)> for (A, B) {
+    fn from_args(first: JsValue, second: JsValue, _: JsValue) -> Option<Self> {
+        Some((A::from_arg(first)?, B::from_arg(second)?))
+    }
+}
+
+impl<MA, MB, MC, A: EventArg<MA>, B: EventArg<MB>, C: EventArg<MC>> EventArgs<(MA, MB, MC)>
+    for (A, B, C)
+{
+    fn from_args(first: JsValue, second: JsValue, third: JsValue) -> Option<Self> {
+        Some((
+            A::from_arg(first)?,
+            B::from_arg(second)?,
+            C::from_arg(third)?,
+        ))
+    }
+}
+
+type Listener = dyn FnMut(JsValue, JsValue, JsValue);
+
+struct Buffer<A> {
+    events: VecDeque<A>,
+    capacity: usize,
+    dropped: u64,
+    skipped: u64,
+    waker: Option<Waker>,
+}
+
+/// A stream of the events fired by an [`EventTarget`].
+///
+/// Created by [`EventTarget::stream`]. Events that arrive while the buffer
+/// is full displace the oldest buffered event, and events whose arguments
+/// don't convert to `A` are skipped. Dropping the stream removes its listener.
+pub struct EventStream<A> {
+    buffer: Rc<RefCell<Buffer<A>>>,
+    _listener: ListenerHandle<Listener>,
+}
+
+impl<A> EventStream<A> {
+    /// The number of events that were discarded because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.buffer.borrow().dropped
+    }
+
+    /// The number of events that were skipped because their arguments could
+    /// not be converted.
+    pub fn skipped(&self) -> u64 {
+        self.buffer.borrow().skipped
+    }
+}
+
+impl<A> Stream for EventStream<A> {
+    type Item = A;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A>> {
+        let mut buffer = self.buffer.borrow_mut();
+        match buffer.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.borrow().events.len(), None)
+    }
+}
+
+impl<A> fmt::Debug for EventStream<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buffer = self.buffer.borrow();
+        f.debug_struct("EventStream")
+            .field("buffered", &buffer.events.len())
+            .field("capacity", &buffer.capacity)
+            .field("dropped", &buffer.dropped)
+            .field("skipped", &buffer.skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventTarget {
+    /// Subscribes to this event and returns its events as a [`Stream`].
+    ///
+    /// At most `capacity` events are buffered until they are polled. The
+    /// argument types are usually inferred from the type of the stream, e.g.
+    /// `let updates: EventStream<(TabId, TabChangeInfo, Tab)> = tabs.on_updated().stream(16);`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn stream<A: EventArgs<M>, M>(&self, capacity: usize) -> EventStream<A> {
+        assert!(capacity > 0, "event stream capacity must not be zero");
+        let buffer = Rc::new(RefCell::new(Buffer {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+            skipped: 0,
+            waker: None,
+        }));
+        let closure = {
+            let buffer = Rc::clone(&buffer);
+            Closure::<Listener>::new(move |first, second, third| {
+                let mut buffer = buffer.borrow_mut();
+                let Some(event) = A::from_args(first, second, third) else {
+                    buffer.skipped += 1;
+                    return;
+                };
+                if buffer.events.len() == buffer.capacity {
+                    buffer.events.pop_front();
+                    buffer.dropped += 1;
+                }
+                buffer.events.push_back(event);
+                if let Some(waker) = buffer.waker.take() {
+                    waker.wake();
+                }
+            })
+        };
+        EventStream {
+            buffer,
+            _listener: self.listen(closure),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdleState, Tab, TabChangeInfo, TabId, WindowId};
+    use js_sys::Object;
+
+    fn assert_event_args<A: EventArgs<M>, M>() {}
+
+    #[test]
+    fn event_args() {
+        assert_event_args::<(), _>();
+        assert_event_args::<(Tab,), _>();
+        assert_event_args::<(TabId, TabChangeInfo, Tab), _>();
+        assert_event_args::<(Object, String), _>();
+        assert_event_args::<(WindowId,), _>();
+        assert_event_args::<(IdleState,), _>();
+        assert_event_args::<(JsValue, Option<String>, f64), _>();
+    }
+
+    // Run with `wasm-pack test --node` or `cargo test --target wasm32-unknown-unknown`.
+    #[cfg(target_arch = "wasm32")]
+    mod wasm {
+        use super::*;
+        use js_sys::{Function, Reflect};
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        /// An `EventTarget` with a `fire` method that calls its listeners.
+        fn event_target() -> EventTarget {
+            Function::new_no_args(
+                "const listeners = [];
+                 return {
+                     addListener: (listener) => listeners.push(listener),
+                     removeListener: (listener) => listeners.splice(listeners.indexOf(listener), 1),
+                     fire: (...args) => listeners.forEach((listener) => listener(...args)),
+                 };",
+            )
+            .call0(&JsValue::NULL)
+            .unwrap()
+            .unchecked_into()
+        }
+
+        fn fire(target: &EventTarget, first: JsValue, second: JsValue) {
+            let fire: Function = Reflect::get(target, &"fire".into())
+                .unwrap()
+                .unchecked_into();
+            fire.call2(target, &first, &second).unwrap();
+        }
+
+        fn poll<A>(stream: &mut EventStream<A>) -> Poll<Option<A>> {
+            let mut cx = Context::from_waker(Waker::noop());
+            Pin::new(stream).poll_next(&mut cx)
+        }
+
+        #[wasm_bindgen_test]
+        fn event_arg_conversions() {
+            assert_eq!(String::from_arg("a".into()), Some("a".to_owned()));
+            assert_eq!(String::from_arg(1.into()), None);
+            assert_eq!(TabId::from_arg(7.into()), Some(TabId(7)));
+            assert_eq!(TabId::from_arg(JsValue::UNDEFINED), None);
+            assert_eq!(
+                IdleState::from_arg("locked".into()),
+                Some(IdleState::Locked)
+            );
+            assert_eq!(Option::<u32>::from_arg(JsValue::UNDEFINED), Some(None));
+            assert_eq!(Option::<u32>::from_arg(JsValue::NULL), Some(None));
+            assert_eq!(Option::<u32>::from_arg(3.into()), Some(Some(3)));
+            assert_eq!(Option::<u32>::from_arg("3".into()), None);
+            assert_eq!(
+                <(TabId, bool)>::from_args(1.into(), JsValue::UNDEFINED, JsValue::UNDEFINED),
+                None
+            );
+        }
+
+        #[wasm_bindgen_test]
+        fn stream_skips_mismatched_events() {
+            let target = event_target();
+            let mut stream: EventStream<(TabId, Option<String>)> = target.stream(4);
+            fire(&target, 1.into(), "a".into());
+            fire(&target, JsValue::UNDEFINED, "b".into());
+            fire(&target, 2.into(), JsValue::UNDEFINED);
+            fire(&target, 3.into(), 4.into());
+            assert_eq!(stream.skipped(), 2);
+            assert_eq!(
+                poll(&mut stream),
+                Poll::Ready(Some((TabId(1), Some("a".to_owned()))))
+            );
+            assert_eq!(poll(&mut stream), Poll::Ready(Some((TabId(2), None))));
+            assert_eq!(poll(&mut stream), Poll::Pending);
+        }
+    }
+}
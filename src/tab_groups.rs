@@ -0,0 +1,275 @@
+//! Bindings to the `tabGroups` API.
+
+use crate::{EventTarget, GroupId, WindowId};
+use js_sys::{Array, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/#type-Color
+    /// The color of a tab group.
+    TabGroupColor {
+        Grey = "grey",
+        Blue = "blue",
+        Red = "red",
+        Yellow = "yellow",
+        Green = "green",
+        Pink = "pink",
+        Purple = "purple",
+        Cyan = "cyan",
+        Orange = "orange",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/#type-TabGroup
+    #[derive(Debug, Clone)]
+    pub type TabGroup;
+
+    // The ID of the group. Unique within a browser session.
+    #[wasm_bindgen(method, getter)]
+    pub fn id(this: &TabGroup) -> GroupId;
+
+    // Whether the group is collapsed, hiding its tabs.
+    #[wasm_bindgen(method, getter)]
+    pub fn collapsed(this: &TabGroup) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn title(this: &TabGroup) -> Option<String>;
+
+    // The ID of the window that contains the group.
+    #[wasm_bindgen(method, getter, js_name = windowId)]
+    pub fn window_id(this: &TabGroup) -> WindowId;
+
+    // Whether the group is shared with other users (Chromium only).
+    #[wasm_bindgen(method, getter)]
+    pub fn shared(this: &TabGroup) -> Option<bool>;
+
+    #[wasm_bindgen(method, getter, js_name = color)]
+    fn color_js(this: &TabGroup) -> String;
+}
+
+impl TabGroup {
+    /// The color of the group.
+    pub fn color(&self) -> TabGroupColor {
+        TabGroupColor::from(self.color_js())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/#method-query
+    /// Properties that the groups returned by [`TabGroups::query`] must match.
+    ///
+    /// Properties that are not set are not checked.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabGroupQueryInfo;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_collapsed(this: &TabGroupQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_color(this: &TabGroupQueryInfo, value: JsValue);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &TabGroupQueryInfo, value: &str);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabGroupQueryInfo, value: WindowId);
+}
+
+impl TabGroupQueryInfo {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the groups are collapsed.
+    pub fn collapsed(&mut self, value: bool) -> &mut Self {
+        self.set_collapsed(value);
+        self
+    }
+
+    /// The color of the groups.
+    pub fn color(&mut self, value: TabGroupColor) -> &mut Self {
+        self.set_color(value.into());
+        self
+    }
+
+    /// Match group titles against a pattern.
+    pub fn title(&mut self, pattern: &str) -> &mut Self {
+        self.set_title(pattern);
+        self
+    }
+
+    /// The ID of the parent window, or `WINDOW_ID_CURRENT` for the current window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+}
+
+impl Default for TabGroupQueryInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/#method-update
+    /// The properties [`TabGroups::update`] changes.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabGroupUpdateProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_collapsed(this: &TabGroupUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_color(this: &TabGroupUpdateProperties, value: JsValue);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &TabGroupUpdateProperties, value: &str);
+}
+
+impl TabGroupUpdateProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the group is collapsed.
+    pub fn collapsed(&mut self, value: bool) -> &mut Self {
+        self.set_collapsed(value);
+        self
+    }
+
+    /// The color of the group.
+    pub fn color(&mut self, value: TabGroupColor) -> &mut Self {
+        self.set_color(value.into());
+        self
+    }
+
+    /// The title of the group.
+    pub fn title(&mut self, value: &str) -> &mut Self {
+        self.set_title(value);
+        self
+    }
+}
+
+impl Default for TabGroupUpdateProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/#method-move
+    /// Where [`TabGroups::move_`] moves the group to.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabGroupMoveProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_index(this: &TabGroupMoveProperties, value: i32);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabGroupMoveProperties, value: WindowId);
+}
+
+impl TabGroupMoveProperties {
+    /// The position to move the group to, `-1` for the end of the window.
+    pub fn new(index: i32) -> Self {
+        let properties: Self = Object::new().unchecked_into();
+        properties.set_index(index);
+        properties
+    }
+
+    /// The window to move the group to, defaults to its current window.
+    ///
+    /// Only windows of type `normal` can contain groups.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabGroups/
+    pub type TabGroups;
+
+    #[wasm_bindgen(method, getter, js_name = TAB_GROUP_ID_NONE)]
+    pub fn tab_group_id_none(this: &TabGroups) -> GroupId;
+
+    // Retrieves details about the group.
+    #[wasm_bindgen(catch, method)]
+    pub async fn get(this: &TabGroups, group_id: GroupId) -> Result<TabGroup, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = query)]
+    async fn query_js(this: &TabGroups, info: &TabGroupQueryInfo) -> Result<Array, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = update)]
+    async fn update_js(
+        this: &TabGroups,
+        group_id: GroupId,
+        properties: &TabGroupUpdateProperties,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = move)]
+    async fn move_js(
+        this: &TabGroups,
+        group_id: GroupId,
+        properties: &TabGroupMoveProperties,
+    ) -> Result<JsValue, JsValue>;
+
+    // Fired when a group is created. The listener receives the `TabGroup`.
+    #[wasm_bindgen(method, getter, js_name = onCreated)]
+    pub fn on_created(this: &TabGroups) -> EventTarget;
+
+    // Fired when a group is updated. The listener receives the `TabGroup`.
+    #[wasm_bindgen(method, getter, js_name = onUpdated)]
+    pub fn on_updated(this: &TabGroups) -> EventTarget;
+
+    // Fired when a group is moved within a window. The listener receives the `TabGroup`.
+    #[wasm_bindgen(method, getter, js_name = onMoved)]
+    pub fn on_moved(this: &TabGroups) -> EventTarget;
+
+    // Fired when a group is closed, either by the user or because all of its
+    // tabs were removed. The listener receives the `TabGroup`.
+    #[wasm_bindgen(method, getter, js_name = onRemoved)]
+    pub fn on_removed(this: &TabGroups) -> EventTarget;
+}
+
+impl TabGroups {
+    /// Retrieves all groups that match the given properties.
+    pub async fn query(&self, info: &TabGroupQueryInfo) -> Result<Vec<TabGroup>, JsValue> {
+        let groups = self.query_js(info).await?;
+        Ok(groups.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    /// Modifies the properties of a group.
+    ///
+    /// Returns `None` if the extension can't see the group's details.
+    pub async fn update(
+        &self,
+        group_id: GroupId,
+        properties: &TabGroupUpdateProperties,
+    ) -> Result<Option<TabGroup>, JsValue> {
+        let group = self.update_js(group_id, properties).await?;
+        Ok((!group.is_undefined()).then(|| group.unchecked_into()))
+    }
+
+    /// Moves a group and its tabs within or to another window.
+    ///
+    /// Returns `None` if the extension can't see the group's details.
+    pub async fn move_(
+        &self,
+        group_id: GroupId,
+        properties: &TabGroupMoveProperties,
+    ) -> Result<Option<TabGroup>, JsValue> {
+        let group = self.move_js(group_id, properties).await?;
+        Ok((!group.is_undefined()).then(|| group.unchecked_into()))
+    }
+}
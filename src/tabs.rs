@@ -1,22 +1,31 @@
 // https://developer.chrome.com/docs/extensions/reference/tabs/
 
-use crate::EventTarget;
-use js_sys::Object;
-use wasm_bindgen::prelude::*;
+use crate::{EventTarget, Window, WindowId, WindowType};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_number_newtype! {
+    /// The tab's ID.
+    ///
+    /// Tab IDs are unique within a browser session.
+    TabId(i32) // `TAB_ID_NONE` has value `-1` so we have to use i32
+}
 
-/// The tab's ID.
-///
-/// Tab IDs are unique within a browser session.
-type TabId = i32; // `TAB_ID_NONE` has value `-1` so we have to use i32
+/// An ID which represents the absence of a browser tab.
+pub const TAB_ID_NONE: TabId = TabId(-1);
 
-/// The ID of the window that hosts a tab.
-type WindowId = i32;
+js_number_newtype! {
+    /// The ID of the group that the tab belongs to.
+    GroupId(i32)
+}
 
-/// The ID of the group that the tab belongs to.
-type GroupId = i32;
+/// An ID that represents the absence of a group.
+pub const TAB_GROUP_ID_NONE: GroupId = GroupId(-1);
 
-/// Zero-based index of the tab within its window.
-type TabIndex = u32;
+js_number_newtype! {
+    /// Zero-based index of the tab within its window.
+    TabIndex(u32)
+}
 
 #[wasm_bindgen]
 extern "C" {
@@ -101,8 +110,38 @@ extern "C" {
     #[wasm_bindgen(method, getter, js_name = extensionId)]
     pub fn extension_id(this: &TabMutedInfo) -> Option<String>;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn reason(this: &TabMutedInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = reason)]
+    fn reason_js(this: &TabMutedInfo) -> Option<String>;
+}
+
+impl TabMutedInfo {
+    /// The reason the tab was muted or unmuted. Not set if the tab's mute state has never been changed.
+    pub fn reason(&self) -> Option<MutedInfoReason> {
+        self.reason_js().map(MutedInfoReason::from)
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#type-MutedInfoReason
+    /// An event that caused a muted state change.
+    MutedInfoReason {
+        /// A user input action set the muted state.
+        User = "user",
+        /// Tab capture was started, forcing a muted state change.
+        Capture = "capture",
+        /// An extension set the muted state.
+        Extension = "extension",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#type-TabStatus
+    /// The tab's loading status.
+    TabStatus {
+        Unloaded = "unloaded",
+        Loading = "loading",
+        Complete = "complete",
+    }
 }
 
 #[wasm_bindgen]
@@ -117,30 +156,15 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn audible(this: &Tab) -> Option<bool>;
 
-    #[cfg(not(feature = "firefox"))]
-    #[wasm_bindgen(method, getter, js_name = autoDiscardable)]
-    pub fn auto_discardable(this: &Tab) -> bool;
-
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = autoDiscardable)]
     pub fn auto_discardable(this: &Tab) -> Option<bool>;
 
-    #[cfg(not(feature = "firefox"))]
-    #[wasm_bindgen(method, getter)]
-    pub fn discarded(this: &Tab) -> bool;
-
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter)]
     pub fn discarded(this: &Tab) -> Option<bool>;
 
     #[wasm_bindgen(method, getter, js_name = favIconUrl)]
     pub fn fav_icon_url(this: &Tab) -> Option<String>;
 
-    #[cfg(not(feature = "firefox"))]
-    #[wasm_bindgen(method, getter, js_name = groupId)]
-    pub fn group_id(this: &Tab) -> GroupId;
-
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = groupId)]
     pub fn group_id(this: &Tab) -> Option<GroupId>;
 
@@ -159,14 +183,9 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn index(this: &Tab) -> TabIndex;
 
-    #[cfg(not(feature = "firefox"))]
     #[wasm_bindgen(method, getter, js_name = mutedInfo)]
     pub fn muted_info(this: &Tab) -> Option<TabMutedInfo>;
 
-    #[cfg(feature = "firefox")]
-    #[wasm_bindgen(method, getter, js_name = mutedInfo)]
-    pub fn muted_info(this: &Tab) -> TabMutedInfo;
-
     #[wasm_bindgen(method, getter, js_name = openerTabId)]
     pub fn opener_tab_id(this: &Tab) -> Option<TabId>;
 
@@ -179,8 +198,8 @@ extern "C" {
     #[wasm_bindgen(method, getter, js_name = sessionId)]
     pub fn session_id(this: &Tab) -> Option<String>;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn status(this: &Tab) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = status)]
+    fn status_js(this: &Tab) -> Option<String>;
 
     #[wasm_bindgen(method, getter)]
     pub fn title(this: &Tab) -> Option<String>;
@@ -194,36 +213,35 @@ extern "C" {
     #[wasm_bindgen(method, getter, js_name = windowId)]
     pub fn window_id(this: &Tab) -> WindowId;
 
-    // --- Firefox only --- //
+    // --- Firefox only (`None` in other browsers) --- //
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter)]
     pub fn attention(this: &Tab) -> Option<bool>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = cookieStoreId)]
     pub fn cookie_store_id(this: &Tab) -> Option<String>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter)]
-    pub fn hidden(this: &Tab) -> bool;
+    pub fn hidden(this: &Tab) -> Option<bool>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = isArticle)]
-    pub fn is_article(this: &Tab) -> bool;
+    pub fn is_article(this: &Tab) -> Option<bool>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = isInReaderMode)]
-    pub fn is_in_reader_mode(this: &Tab) -> bool;
+    pub fn is_in_reader_mode(this: &Tab) -> Option<bool>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = lastAccessed)]
-    pub fn last_accessed(this: &Tab) -> f64;
+    pub fn last_accessed(this: &Tab) -> Option<f64>;
 
-    #[cfg(feature = "firefox")]
     #[wasm_bindgen(method, getter, js_name = successorId)]
     pub fn successor_id(this: &Tab) -> Option<TabId>;
+}
 
+impl Tab {
+    /// The tab's loading status.
+    pub fn status(&self) -> Option<TabStatus> {
+        self.status_js().map(TabStatus::from)
+    }
 }
 
 #[wasm_bindgen]
@@ -256,7 +274,7 @@ extern "C" {
     ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method)]
-    pub async fn create(this: &Tabs, info: &Object) -> Result<JsValue, JsValue>;
+    pub async fn create(this: &Tabs, properties: &TabCreateProperties) -> Result<Tab, JsValue>;
 
     #[wasm_bindgen(catch, method)]
     pub async fn discard(this: &Tabs, tab_ids: &JsValue) -> Result<JsValue, JsValue>;
@@ -276,11 +294,18 @@ extern "C" {
     #[wasm_bindgen(catch, method, js_name = getZoomSettings)]
     pub async fn get_zoom_settings(this: &Tabs, tab_id: Option<TabId>) -> Result<JsValue, JsValue>;
 
+    // Adds tabs to a group, or creates a new group, and returns the group's ID.
+    #[wasm_bindgen(catch, method)]
+    pub async fn group(this: &Tabs, options: &TabGroupOptions) -> Result<GroupId, JsValue>;
+
     #[wasm_bindgen(catch, method)]
     pub async fn hide(this: &Tabs, tab_ids: &JsValue) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method)]
-    pub async fn highlight(this: &Tabs, info: &Object) -> Result<JsValue, JsValue>;
+    pub async fn highlight(
+        this: &Tabs,
+        properties: &TabHighlightProperties,
+    ) -> Result<Window, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = insertCSS)]
     pub async fn insert_css(
@@ -290,7 +315,11 @@ extern "C" {
     ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = move)]
-    pub async fn move_(this: &Tabs, tab_ids: &JsValue, info: &Object) -> Result<JsValue, JsValue>;
+    async fn move_js(
+        this: &Tabs,
+        tab_ids: &JsValue,
+        properties: &TabMoveProperties,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = moveInSuccession)]
     pub async fn move_in_succession(
@@ -306,8 +335,8 @@ extern "C" {
     #[wasm_bindgen(catch, method, js_name = printPreview)]
     pub async fn print_preview(this: &Tabs) -> Result<JsValue, JsValue>;
 
-    #[wasm_bindgen(catch, method)]
-    pub async fn query(this: &Tabs, info: &Object) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch, method, js_name = query)]
+    async fn query_js(this: &Tabs, info: &TabQueryInfo) -> Result<Array, JsValue>;
 
     #[wasm_bindgen(catch, method)]
     pub async fn reload(
@@ -358,12 +387,15 @@ extern "C" {
     pub async fn toggle_reader_mode(this: &Tabs, tab_id: Option<TabId>)
         -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(catch, method, js_name = ungroup)]
+    async fn ungroup_js(this: &Tabs, tab_ids: &Array) -> Result<(), JsValue>;
+
     #[wasm_bindgen(catch, method)]
     pub async fn update(
         this: &Tabs,
         tab_id: Option<TabId>,
-        info: &Object,
-    ) -> Result<JsValue, JsValue>;
+        properties: &TabUpdateProperties,
+    ) -> Result<Tab, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = detectLanguage)]
     pub async fn detect_language(this: &Tabs, tab_id: Option<TabId>) -> Result<JsValue, JsValue>;
@@ -399,6 +431,576 @@ extern "C" {
     pub fn on_zoom_change(this: &Tabs) -> EventTarget;
 }
 
+impl Tabs {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-query
+    pub async fn query(&self, info: &TabQueryInfo) -> Result<Vec<Tab>, JsValue> {
+        let tabs = self.query_js(info).await?;
+        Ok(tabs.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-move
+    pub async fn move_(
+        &self,
+        tab_ids: &JsValue,
+        properties: &TabMoveProperties,
+    ) -> Result<Vec<Tab>, JsValue> {
+        let moved = self.move_js(tab_ids, properties).await?;
+        Ok(match moved.dyn_into::<Array>() {
+            Ok(tabs) => tabs.iter().map(JsCast::unchecked_into).collect(),
+            Err(tab) => vec![tab.unchecked_into()],
+        })
+    }
+
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-ungroup
+    /// Removes tabs from their groups. Empty groups are deleted.
+    pub async fn ungroup(&self, tab_ids: &[TabId]) -> Result<(), JsValue> {
+        let tab_ids: Array = tab_ids.iter().copied().map(JsValue::from).collect();
+        self.ungroup_js(&tab_ids).await
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-query
+    /// Properties that the tabs returned by [`Tabs::query`] must match.
+    ///
+    /// Properties that are not set are not checked.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabQueryInfo;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_active(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_audible(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter = autoDiscardable)]
+    fn set_auto_discardable(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter = currentWindow)]
+    fn set_current_window(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_discarded(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter = groupId)]
+    fn set_group_id(this: &TabQueryInfo, value: GroupId);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_highlighted(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_index(this: &TabQueryInfo, value: TabIndex);
+
+    #[wasm_bindgen(method, setter = lastFocusedWindow)]
+    fn set_last_focused_window(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_muted(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_pinned(this: &TabQueryInfo, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_status(this: &TabQueryInfo, value: JsValue);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &TabQueryInfo, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &TabQueryInfo, value: &Array);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabQueryInfo, value: WindowId);
+
+    #[wasm_bindgen(method, setter = windowType)]
+    fn set_window_type(this: &TabQueryInfo, value: JsValue);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = cookieStoreId)]
+    fn set_cookie_store_id(this: &TabQueryInfo, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_hidden(this: &TabQueryInfo, value: bool);
+}
+
+impl TabQueryInfo {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the tabs are active in their windows.
+    pub fn active(&mut self, value: bool) -> &mut Self {
+        self.set_active(value);
+        self
+    }
+
+    /// Whether the tabs are audible.
+    pub fn audible(&mut self, value: bool) -> &mut Self {
+        self.set_audible(value);
+        self
+    }
+
+    /// Whether the tabs can be discarded automatically by the browser.
+    pub fn auto_discardable(&mut self, value: bool) -> &mut Self {
+        self.set_auto_discardable(value);
+        self
+    }
+
+    /// Whether the tabs are in the current window.
+    pub fn current_window(&mut self, value: bool) -> &mut Self {
+        self.set_current_window(value);
+        self
+    }
+
+    /// Whether the tabs are discarded.
+    pub fn discarded(&mut self, value: bool) -> &mut Self {
+        self.set_discarded(value);
+        self
+    }
+
+    /// The ID of the group that the tabs are in.
+    pub fn group_id(&mut self, value: GroupId) -> &mut Self {
+        self.set_group_id(value);
+        self
+    }
+
+    /// Whether the tabs are highlighted.
+    pub fn highlighted(&mut self, value: bool) -> &mut Self {
+        self.set_highlighted(value);
+        self
+    }
+
+    /// The position of the tabs within their windows.
+    pub fn index(&mut self, value: TabIndex) -> &mut Self {
+        self.set_index(value);
+        self
+    }
+
+    /// Whether the tabs are in the last focused window.
+    pub fn last_focused_window(&mut self, value: bool) -> &mut Self {
+        self.set_last_focused_window(value);
+        self
+    }
+
+    /// Whether the tabs are muted.
+    pub fn muted(&mut self, value: bool) -> &mut Self {
+        self.set_muted(value);
+        self
+    }
+
+    /// Whether the tabs are pinned.
+    pub fn pinned(&mut self, value: bool) -> &mut Self {
+        self.set_pinned(value);
+        self
+    }
+
+    /// The tab loading status.
+    pub fn status(&mut self, value: TabStatus) -> &mut Self {
+        self.set_status(value.into());
+        self
+    }
+
+    /// Match page titles against a pattern. Ignored without the `tabs` permission.
+    pub fn title(&mut self, pattern: &str) -> &mut Self {
+        self.set_title(pattern);
+        self
+    }
+
+    /// Match tabs against one or more URL patterns. Fragment identifiers are not matched.
+    /// Ignored without the `tabs` permission.
+    pub fn url<S: AsRef<str>>(&mut self, patterns: &[S]) -> &mut Self {
+        let patterns: Array = patterns
+            .iter()
+            .map(|pattern| JsValue::from_str(pattern.as_ref()))
+            .collect();
+        self.set_url(&patterns);
+        self
+    }
+
+    /// The ID of the parent window, or `WINDOW_ID_CURRENT` for the current window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+
+    /// The type of window the tabs are in.
+    pub fn window_type(&mut self, value: WindowType) -> &mut Self {
+        self.set_window_type(value.into());
+        self
+    }
+
+    /// The cookie store (container) of the tabs (Firefox only).
+    pub fn cookie_store_id(&mut self, value: &str) -> &mut Self {
+        self.set_cookie_store_id(value);
+        self
+    }
+
+    /// Whether the tabs are hidden (Firefox only).
+    pub fn hidden(&mut self, value: bool) -> &mut Self {
+        self.set_hidden(value);
+        self
+    }
+}
+
+impl Default for TabQueryInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-create
+    /// Properties of the tab created by [`Tabs::create`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabCreateProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_active(this: &TabCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_index(this: &TabCreateProperties, value: TabIndex);
+
+    #[wasm_bindgen(method, setter = openerTabId)]
+    fn set_opener_tab_id(this: &TabCreateProperties, value: TabId);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_pinned(this: &TabCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &TabCreateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabCreateProperties, value: WindowId);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = cookieStoreId)]
+    fn set_cookie_store_id(this: &TabCreateProperties, value: &str);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_discarded(this: &TabCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_muted(this: &TabCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = openInReaderMode)]
+    fn set_open_in_reader_mode(this: &TabCreateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_title(this: &TabCreateProperties, value: &str);
+}
+
+impl TabCreateProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the tab becomes the active tab in its window.
+    pub fn active(&mut self, value: bool) -> &mut Self {
+        self.set_active(value);
+        self
+    }
+
+    /// The position the tab takes in the window.
+    pub fn index(&mut self, value: TabIndex) -> &mut Self {
+        self.set_index(value);
+        self
+    }
+
+    /// The ID of the tab that opened this tab.
+    pub fn opener_tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_opener_tab_id(value);
+        self
+    }
+
+    /// Whether the tab is pinned.
+    pub fn pinned(&mut self, value: bool) -> &mut Self {
+        self.set_pinned(value);
+        self
+    }
+
+    /// The URL to initially navigate the tab to.
+    pub fn url(&mut self, value: &str) -> &mut Self {
+        self.set_url(value);
+        self
+    }
+
+    /// The window in which to create the tab.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+
+    /// The cookie store (container) of the tab (Firefox only).
+    pub fn cookie_store_id(&mut self, value: &str) -> &mut Self {
+        self.set_cookie_store_id(value);
+        self
+    }
+
+    /// Whether the tab is created discarded (Firefox only).
+    pub fn discarded(&mut self, value: bool) -> &mut Self {
+        self.set_discarded(value);
+        self
+    }
+
+    /// Whether the tab is muted (Firefox only).
+    pub fn muted(&mut self, value: bool) -> &mut Self {
+        self.set_muted(value);
+        self
+    }
+
+    /// Whether the tab opens in Reader Mode (Firefox only).
+    pub fn open_in_reader_mode(&mut self, value: bool) -> &mut Self {
+        self.set_open_in_reader_mode(value);
+        self
+    }
+
+    /// The title of a discarded tab (Firefox only).
+    pub fn title(&mut self, value: &str) -> &mut Self {
+        self.set_title(value);
+        self
+    }
+}
+
+impl Default for TabCreateProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-update
+    /// Properties changed by [`Tabs::update`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabUpdateProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_active(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = autoDiscardable)]
+    fn set_auto_discardable(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_highlighted(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_muted(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = openerTabId)]
+    fn set_opener_tab_id(this: &TabUpdateProperties, value: TabId);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_pinned(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &TabUpdateProperties, value: &str);
+
+    // --- Firefox only --- //
+
+    #[wasm_bindgen(method, setter = loadReplace)]
+    fn set_load_replace(this: &TabUpdateProperties, value: bool);
+
+    #[wasm_bindgen(method, setter = successorTabId)]
+    fn set_successor_tab_id(this: &TabUpdateProperties, value: TabId);
+}
+
+impl TabUpdateProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// Whether the tab is active.
+    pub fn active(&mut self, value: bool) -> &mut Self {
+        self.set_active(value);
+        self
+    }
+
+    /// Whether the tab may be discarded automatically by the browser.
+    pub fn auto_discardable(&mut self, value: bool) -> &mut Self {
+        self.set_auto_discardable(value);
+        self
+    }
+
+    /// Adds or removes the tab from the current selection.
+    pub fn highlighted(&mut self, value: bool) -> &mut Self {
+        self.set_highlighted(value);
+        self
+    }
+
+    /// Whether the tab is muted.
+    pub fn muted(&mut self, value: bool) -> &mut Self {
+        self.set_muted(value);
+        self
+    }
+
+    /// The ID of the tab that opened this tab.
+    pub fn opener_tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_opener_tab_id(value);
+        self
+    }
+
+    /// Whether the tab is pinned.
+    pub fn pinned(&mut self, value: bool) -> &mut Self {
+        self.set_pinned(value);
+        self
+    }
+
+    /// A URL to navigate the tab to.
+    pub fn url(&mut self, value: &str) -> &mut Self {
+        self.set_url(value);
+        self
+    }
+
+    /// Whether the new URL replaces the current entry in the session history (Firefox only).
+    pub fn load_replace(&mut self, value: bool) -> &mut Self {
+        self.set_load_replace(value);
+        self
+    }
+
+    /// The ID of the tab activated when this tab is closed (Firefox only).
+    pub fn successor_tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_successor_tab_id(value);
+        self
+    }
+}
+
+impl Default for TabUpdateProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-move
+    /// Where [`Tabs::move_`] moves the tabs to.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabMoveProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_index(this: &TabMoveProperties, value: i32);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabMoveProperties, value: WindowId);
+}
+
+impl TabMoveProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The position to move the tabs to, `-1` for the end of the window.
+    pub fn index(&mut self, value: i32) -> &mut Self {
+        self.set_index(value);
+        self
+    }
+
+    /// The window to move the tabs to, defaults to their current window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+}
+
+impl Default for TabMoveProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-group
+    /// The tabs [`Tabs::group`] groups and the group they are added to.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type TabGroupOptions;
+
+    #[wasm_bindgen(method, setter = tabIds)]
+    fn set_tab_ids(this: &TabGroupOptions, value: &Array);
+
+    #[wasm_bindgen(method, setter = groupId)]
+    fn set_group_id(this: &TabGroupOptions, value: GroupId);
+
+    #[wasm_bindgen(method, setter = createProperties)]
+    fn set_create_properties(this: &TabGroupOptions, value: &Object);
+}
+
+impl TabGroupOptions {
+    pub fn new(tab_ids: &[TabId]) -> Self {
+        let options: Self = Object::new().unchecked_into();
+        options.set_tab_ids(&tab_ids.iter().copied().map(JsValue::from).collect());
+        options
+    }
+
+    /// The group to add the tabs to. A new group is created if not set.
+    pub fn group_id(&mut self, value: GroupId) -> &mut Self {
+        self.set_group_id(value);
+        self
+    }
+
+    /// The window of the new group, defaults to the current window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        let properties = Object::new();
+        // Setting a property of a plain object can't fail.
+        let _ = Reflect::set(&properties, &JsValue::from_str("windowId"), &value.into());
+        self.set_create_properties(&properties);
+        self
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/tabs/#method-highlight
+    /// The tabs selected by [`Tabs::highlight`], called `highlightInfo` in the
+    /// API reference.
+    ///
+    /// [`TabHighlightInfo`] is the payload of `onHighlighted`, and its
+    /// `window_id` getter would clash with the builder method of the same name.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    #[doc(alias = "highlightInfo")]
+    pub type TabHighlightProperties;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_tabs(this: &TabHighlightProperties, value: &Array);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &TabHighlightProperties, value: WindowId);
+}
+
+impl TabHighlightProperties {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The indices of the tabs to highlight.
+    pub fn tabs(&mut self, value: &[TabIndex]) -> &mut Self {
+        self.set_tabs(&value.iter().copied().map(JsValue::from).collect());
+        self
+    }
+
+    /// The window that contains the tabs.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+}
+
+impl Default for TabHighlightProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 extern "C" {
     // https://developer.chrome.com/docs/extensions/reference/tabs/#type-onUpdated-callback-changeInfo
@@ -434,8 +1036,8 @@ extern "C" {
     pub fn pinned(this: &TabChangeInfo) -> Option<bool>;
 
     // The tab's loading status.
-    #[wasm_bindgen(method, getter)]
-    pub fn status(this: &TabChangeInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = status)]
+    fn status_js(this: &TabChangeInfo) -> Option<String>;
 
     // The tab's new title.
     #[wasm_bindgen(method, getter)]
@@ -446,6 +1048,13 @@ extern "C" {
     pub fn url(this: &TabChangeInfo) -> Option<String>;
 }
 
+impl TabChangeInfo {
+    /// The tab's loading status.
+    pub fn status(&self) -> Option<TabStatus> {
+        self.status_js().map(TabStatus::from)
+    }
+}
+
 #[wasm_bindgen]
 extern "C" {
     // https://developer.chrome.com/docs/extensions/reference/tabs/#type-onHighlighted-callback-highlightInfo
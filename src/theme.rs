@@ -1,4 +1,4 @@
-use crate::EventTarget;
+use crate::{EventTarget, WindowId};
 use js_sys::{Array, Promise};
 use wasm_bindgen::{prelude::*, JsCast};
 
@@ -223,7 +223,7 @@ extern "C" {
     pub fn theme(this: &ThemeUpdateInfo) -> Theme;
 
     #[wasm_bindgen(method, getter, js_name = windowId)]
-    pub fn window_id(this: &ThemeUpdateInfo) -> Option<i32>;
+    pub fn window_id(this: &ThemeUpdateInfo) -> Option<WindowId>;
 }
 
 #[wasm_bindgen]
@@ -231,13 +231,13 @@ extern "C" {
     pub type BrowserTheme;
 
     #[wasm_bindgen(method, js_name = getCurrent)]
-    pub fn get_current(this: &BrowserTheme, window_id: Option<i32>) -> Promise;
+    pub fn get_current(this: &BrowserTheme, window_id: Option<WindowId>) -> Promise;
 
     #[wasm_bindgen(method)]
-    pub fn update(this: &BrowserTheme, window_id: Option<i32>, theme: &Theme);
+    pub fn update(this: &BrowserTheme, window_id: Option<WindowId>, theme: &Theme);
 
     #[wasm_bindgen(method)]
-    pub fn reset(this: &BrowserTheme, window_id: Option<i32>);
+    pub fn reset(this: &BrowserTheme, window_id: Option<WindowId>);
 
     #[wasm_bindgen(method, getter, js_name = onUpdated)]
     pub fn on_updated(this: &BrowserTheme) -> EventTarget;
@@ -0,0 +1,36 @@
+//! Bindings to the `topSites` API.
+
+use js_sys::Array;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/topSites/#type-MostVisitedURL
+    /// An entry of the most visited sites.
+    #[derive(Debug, Clone)]
+    pub type MostVisitedUrl;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &MostVisitedUrl) -> String;
+
+    // The title of the page, if known.
+    #[wasm_bindgen(method, getter)]
+    pub fn title(this: &MostVisitedUrl) -> Option<String>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/topSites/
+    pub type TopSites;
+
+    #[wasm_bindgen(catch, method, js_name = get)]
+    async fn get_js(this: &TopSites) -> Result<Array, JsValue>;
+}
+
+impl TopSites {
+    /// Returns the sites shown on the new tab page, most visited first.
+    pub async fn get(&self) -> Result<Vec<MostVisitedUrl>, JsValue> {
+        let sites = self.get_js().await?;
+        Ok(sites.iter().map(JsCast::unchecked_into).collect())
+    }
+}
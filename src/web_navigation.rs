@@ -0,0 +1,412 @@
+//! Bindings to the `webNavigation` API.
+
+use crate::{EventTarget, TabId, UrlFilter};
+use js_sys::{Array, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#type-TransitionType
+    /// Cause of a navigation.
+    TransitionType {
+        Link = "link",
+        Typed = "typed",
+        AutoBookmark = "auto_bookmark",
+        AutoSubframe = "auto_subframe",
+        ManualSubframe = "manual_subframe",
+        Generated = "generated",
+        StartPage = "start_page",
+        FormSubmit = "form_submit",
+        Reload = "reload",
+        Keyword = "keyword",
+        KeywordGenerated = "keyword_generated",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#type-TransitionQualifier
+    /// Additional information about the cause of a navigation.
+    TransitionQualifier {
+        ClientRedirect = "client_redirect",
+        ServerRedirect = "server_redirect",
+        ForwardBack = "forward_back",
+        FromAddressBar = "from_address_bar",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/extensionTypes/#type-FrameType
+    /// The type of a frame.
+    FrameType {
+        OutermostFrame = "outermost_frame",
+        FencedFrame = "fenced_frame",
+        SubFrame = "sub_frame",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/extensionTypes/#type-DocumentLifecycle
+    /// The lifecycle state of a document.
+    DocumentLifecycle {
+        Prerender = "prerender",
+        Active = "active",
+        Cached = "cached",
+        PendingDeletion = "pending_deletion",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#method-getFrame
+    /// Identifies the frame passed to [`WebNavigation::get_frame`].
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type GetFrameDetails;
+
+    #[wasm_bindgen(method, setter = tabId)]
+    fn set_tab_id(this: &GetFrameDetails, value: TabId);
+
+    #[wasm_bindgen(method, setter = frameId)]
+    fn set_frame_id(this: &GetFrameDetails, value: i32);
+
+    #[wasm_bindgen(method, setter = documentId)]
+    fn set_document_id(this: &GetFrameDetails, value: &str);
+}
+
+impl GetFrameDetails {
+    pub fn new() -> Self {
+        Object::new().unchecked_into()
+    }
+
+    /// The ID of the tab in which the frame is.
+    pub fn tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_tab_id(value);
+        self
+    }
+
+    /// The ID of the frame in the given tab.
+    pub fn frame_id(&mut self, value: i32) -> &mut Self {
+        self.set_frame_id(value);
+        self
+    }
+
+    /// The UUID of the document. Can be used instead of the tab and frame ID.
+    pub fn document_id(&mut self, value: &str) -> &mut Self {
+        self.set_document_id(value);
+        self
+    }
+}
+
+impl Default for GetFrameDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#method-getFrame
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#method-getAllFrames
+    /// Information about a frame returned by [`WebNavigation::get_frame`]
+    /// and [`WebNavigation::get_all_frames`].
+    #[derive(Debug, Clone)]
+    pub type FrameDetails;
+
+    // The URL currently associated with this frame.
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &FrameDetails) -> String;
+
+    // True if the last navigation in this frame was interrupted by an error.
+    #[wasm_bindgen(method, getter, js_name = errorOccurred)]
+    pub fn error_occurred(this: &FrameDetails) -> bool;
+
+    // The ID of the frame. Only set by `getAllFrames`.
+    #[wasm_bindgen(method, getter, js_name = frameId)]
+    pub fn frame_id(this: &FrameDetails) -> Option<i32>;
+
+    // The ID of the parent frame, or -1 for the main frame.
+    #[wasm_bindgen(method, getter, js_name = parentFrameId)]
+    pub fn parent_frame_id(this: &FrameDetails) -> i32;
+
+    // The ID of the process that runs the renderer for this frame. Only set by `getAllFrames`.
+    #[wasm_bindgen(method, getter, js_name = processId)]
+    pub fn process_id(this: &FrameDetails) -> Option<i32>;
+
+    // A UUID of the document loaded (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = documentId)]
+    pub fn document_id(this: &FrameDetails) -> Option<String>;
+
+    // A UUID of the parent document owning this frame (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = parentDocumentId)]
+    pub fn parent_document_id(this: &FrameDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = documentLifecycle)]
+    fn document_lifecycle_js(this: &FrameDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = frameType)]
+    fn frame_type_js(this: &FrameDetails) -> Option<String>;
+}
+
+impl FrameDetails {
+    /// The lifecycle the document is in (Chromium only).
+    pub fn document_lifecycle(&self) -> Option<DocumentLifecycle> {
+        self.document_lifecycle_js().map(DocumentLifecycle::from)
+    }
+
+    /// The type of frame (Chromium only).
+    pub fn frame_type(&self) -> Option<FrameType> {
+        self.frame_type_js().map(FrameType::from)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#event-onBeforeNavigate
+    /// The details passed to the listeners of `onBeforeNavigate`, `onCommitted`,
+    /// `onDOMContentLoaded`, `onCompleted`, `onErrorOccurred`,
+    /// `onHistoryStateUpdated` and `onReferenceFragmentUpdated`.
+    ///
+    /// Fields that only some of these events report are optional.
+    #[derive(Debug, Clone)]
+    pub type NavigationDetails;
+
+    // The ID of the tab in which the navigation occurs.
+    #[wasm_bindgen(method, getter, js_name = tabId)]
+    pub fn tab_id(this: &NavigationDetails) -> TabId;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &NavigationDetails) -> String;
+
+    // 0 indicates the navigation happens in the tab content window.
+    #[wasm_bindgen(method, getter, js_name = frameId)]
+    pub fn frame_id(this: &NavigationDetails) -> i32;
+
+    // The ID of the parent frame, or -1 for the main frame.
+    #[wasm_bindgen(method, getter, js_name = parentFrameId)]
+    pub fn parent_frame_id(this: &NavigationDetails) -> Option<i32>;
+
+    // The ID of the process that runs the renderer for this frame.
+    #[wasm_bindgen(method, getter, js_name = processId)]
+    pub fn process_id(this: &NavigationDetails) -> Option<i32>;
+
+    // The time when the event fired, in milliseconds since the epoch.
+    #[wasm_bindgen(method, getter, js_name = timeStamp)]
+    pub fn time_stamp(this: &NavigationDetails) -> f64;
+
+    // A UUID of the document loaded (Chromium only, not set by `onBeforeNavigate`).
+    #[wasm_bindgen(method, getter, js_name = documentId)]
+    pub fn document_id(this: &NavigationDetails) -> Option<String>;
+
+    // A UUID of the parent document owning this frame (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = parentDocumentId)]
+    pub fn parent_document_id(this: &NavigationDetails) -> Option<String>;
+
+    // The error description. Only set by `onErrorOccurred`.
+    #[wasm_bindgen(method, getter)]
+    pub fn error(this: &NavigationDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = documentLifecycle)]
+    fn document_lifecycle_js(this: &NavigationDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = frameType)]
+    fn frame_type_js(this: &NavigationDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = transitionType)]
+    fn transition_type_js(this: &NavigationDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = transitionQualifiers)]
+    fn transition_qualifiers_js(this: &NavigationDetails) -> Option<Array>;
+}
+
+impl NavigationDetails {
+    /// The lifecycle the document is in (Chromium only).
+    pub fn document_lifecycle(&self) -> Option<DocumentLifecycle> {
+        self.document_lifecycle_js().map(DocumentLifecycle::from)
+    }
+
+    /// The type of frame the navigation occurred in (Chromium only).
+    pub fn frame_type(&self) -> Option<FrameType> {
+        self.frame_type_js().map(FrameType::from)
+    }
+
+    /// Cause of the navigation.
+    ///
+    /// Only set by `onCommitted`, `onHistoryStateUpdated` and
+    /// `onReferenceFragmentUpdated`.
+    pub fn transition_type(&self) -> Option<TransitionType> {
+        self.transition_type_js().map(TransitionType::from)
+    }
+
+    /// Additional information about the cause of the navigation.
+    ///
+    /// Empty for events that don't report a transition.
+    pub fn transition_qualifiers(&self) -> Vec<TransitionQualifier> {
+        self.transition_qualifiers_js()
+            .map(|qualifiers| {
+                qualifiers
+                    .iter()
+                    .filter_map(|qualifier| qualifier.as_string())
+                    .map(TransitionQualifier::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#event-onCreatedNavigationTarget
+    #[derive(Debug, Clone)]
+    pub type CreatedNavigationTargetDetails;
+
+    // The ID of the tab in which the navigation is triggered.
+    #[wasm_bindgen(method, getter, js_name = sourceTabId)]
+    pub fn source_tab_id(this: &CreatedNavigationTargetDetails) -> TabId;
+
+    // The ID of the process that runs the renderer for the source frame.
+    #[wasm_bindgen(method, getter, js_name = sourceProcessId)]
+    pub fn source_process_id(this: &CreatedNavigationTargetDetails) -> Option<i32>;
+
+    // The ID of the frame in which the navigation is triggered.
+    #[wasm_bindgen(method, getter, js_name = sourceFrameId)]
+    pub fn source_frame_id(this: &CreatedNavigationTargetDetails) -> i32;
+
+    // The URL to be opened in the new window.
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &CreatedNavigationTargetDetails) -> String;
+
+    // The ID of the tab in which the URL is opened.
+    #[wasm_bindgen(method, getter, js_name = tabId)]
+    pub fn tab_id(this: &CreatedNavigationTargetDetails) -> TabId;
+
+    // The time when the browser was about to create a new view, in milliseconds since the epoch.
+    #[wasm_bindgen(method, getter, js_name = timeStamp)]
+    pub fn time_stamp(this: &CreatedNavigationTargetDetails) -> f64;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#event-onTabReplaced
+    #[derive(Debug, Clone)]
+    pub type TabReplacedDetails;
+
+    // The ID of the tab that was replaced.
+    #[wasm_bindgen(method, getter, js_name = replacedTabId)]
+    pub fn replaced_tab_id(this: &TabReplacedDetails) -> TabId;
+
+    // The ID of the tab that replaced the old tab.
+    #[wasm_bindgen(method, getter, js_name = tabId)]
+    pub fn tab_id(this: &TabReplacedDetails) -> TabId;
+
+    // The time when the replacement happened, in milliseconds since the epoch.
+    #[wasm_bindgen(method, getter, js_name = timeStamp)]
+    pub fn time_stamp(this: &TabReplacedDetails) -> f64;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/#event-onBeforeNavigate
+    /// The filter passed to [`EventTarget::listen_with_filter`] for the
+    /// navigation events.
+    ///
+    /// Only navigations to URLs that match at least one of the filters fire
+    /// the event.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type NavigationEventFilter;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_url(this: &NavigationEventFilter, value: &Array);
+}
+
+impl NavigationEventFilter {
+    pub fn new(url: &[UrlFilter]) -> Self {
+        let filter: Self = Object::new().unchecked_into();
+        filter.set_url(&url.iter().collect());
+        filter
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webNavigation/
+    pub type WebNavigation;
+
+    #[wasm_bindgen(catch, method, js_name = getFrame)]
+    async fn get_frame_js(
+        this: &WebNavigation,
+        details: &GetFrameDetails,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getAllFrames)]
+    async fn get_all_frames_js(this: &WebNavigation, details: &Object) -> Result<JsValue, JsValue>;
+
+    // Fired when a navigation is about to occur. The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onBeforeNavigate)]
+    pub fn on_before_navigate(this: &WebNavigation) -> EventTarget;
+
+    // Fired when a navigation is committed. The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onCommitted)]
+    pub fn on_committed(this: &WebNavigation) -> EventTarget;
+
+    // Fired when the page's DOM is fully constructed. The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onDOMContentLoaded)]
+    pub fn on_dom_content_loaded(this: &WebNavigation) -> EventTarget;
+
+    // Fired when a document, including its resources, is completely loaded.
+    // The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onCompleted)]
+    pub fn on_completed(this: &WebNavigation) -> EventTarget;
+
+    // Fired when an error occurs and a navigation is aborted. The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onErrorOccurred)]
+    pub fn on_error_occurred(this: &WebNavigation) -> EventTarget;
+
+    // Fired when the URL of a frame was updated through the history API.
+    // The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onHistoryStateUpdated)]
+    pub fn on_history_state_updated(this: &WebNavigation) -> EventTarget;
+
+    // Fired when the reference fragment of a frame was updated.
+    // The listener receives `NavigationDetails`.
+    #[wasm_bindgen(method, getter, js_name = onReferenceFragmentUpdated)]
+    pub fn on_reference_fragment_updated(this: &WebNavigation) -> EventTarget;
+
+    // Fired when a new window or tab is created to host a navigation.
+    // The listener receives `CreatedNavigationTargetDetails`.
+    #[wasm_bindgen(method, getter, js_name = onCreatedNavigationTarget)]
+    pub fn on_created_navigation_target(this: &WebNavigation) -> EventTarget;
+
+    // Fired when the contents of a tab is replaced by a different (usually
+    // previously pre-rendered) tab. The listener receives `TabReplacedDetails`.
+    #[wasm_bindgen(method, getter, js_name = onTabReplaced)]
+    pub fn on_tab_replaced(this: &WebNavigation) -> EventTarget;
+}
+
+impl WebNavigation {
+    /// Retrieves information about the given frame.
+    ///
+    /// Returns `None` if the frame does not exist.
+    pub async fn get_frame(
+        &self,
+        details: &GetFrameDetails,
+    ) -> Result<Option<FrameDetails>, JsValue> {
+        let frame = self.get_frame_js(details).await?;
+        Ok((!frame.is_undefined() && !frame.is_null()).then(|| frame.unchecked_into()))
+    }
+
+    /// Retrieves information about all frames of the given tab.
+    ///
+    /// Returns `None` if the tab does not exist.
+    pub async fn get_all_frames(
+        &self,
+        tab_id: TabId,
+    ) -> Result<Option<Vec<FrameDetails>>, JsValue> {
+        let details = Object::new();
+        js_sys::Reflect::set(&details, &JsValue::from_str("tabId"), &tab_id.into())?;
+        let frames = self.get_all_frames_js(&details).await?;
+        Ok(frames
+            .dyn_into::<Array>()
+            .ok()
+            .map(|frames| frames.iter().map(JsCast::unchecked_into).collect()))
+    }
+}
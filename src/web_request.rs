@@ -0,0 +1,342 @@
+//! Bindings to the observational part of the `webRequest` API.
+
+use crate::{DocumentLifecycle, EventTarget, FrameType, TabId, WindowId};
+use js_sys::{Array, ArrayBuffer, Object};
+use wasm_bindgen::{prelude::*, JsCast};
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#type-ResourceType
+    /// How the requested resource will be used.
+    ResourceType {
+        MainFrame = "main_frame",
+        SubFrame = "sub_frame",
+        Stylesheet = "stylesheet",
+        Script = "script",
+        Image = "image",
+        Font = "font",
+        Object = "object",
+        XmlHttpRequest = "xmlhttprequest",
+        Ping = "ping",
+        CspReport = "csp_report",
+        Media = "media",
+        WebSocket = "websocket",
+        WebTransport = "webtransport",
+        WebBundle = "webbundle",
+        Other = "other",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#type-OnBeforeRequestOptions
+    /// Additional information to include in the details passed to a listener.
+    ///
+    /// Each event supports a subset of the values; see the linked reference.
+    ExtraInfoSpec {
+        /// The request is blocked until the listener returns (requires the
+        /// `webRequestBlocking` permission).
+        Blocking = "blocking",
+        /// Like `Blocking`, but the listener receives a callback.
+        AsyncBlocking = "asyncBlocking",
+        /// Include the request headers.
+        RequestHeaders = "requestHeaders",
+        /// Include the response headers.
+        ResponseHeaders = "responseHeaders",
+        /// Include headers that are hidden by default (Chromium only).
+        ExtraHeaders = "extraHeaders",
+        /// Include the request body in `onBeforeRequest`.
+        RequestBody = "requestBody",
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#type-RequestFilter
+    /// Selects the requests an event listener is invoked for.
+    #[wasm_bindgen(extends = Object)]
+    #[derive(Debug, Clone)]
+    pub type RequestFilter;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_urls(this: &RequestFilter, value: &Array);
+
+    #[wasm_bindgen(method, setter)]
+    fn set_types(this: &RequestFilter, value: &Array);
+
+    #[wasm_bindgen(method, setter = tabId)]
+    fn set_tab_id(this: &RequestFilter, value: TabId);
+
+    #[wasm_bindgen(method, setter = windowId)]
+    fn set_window_id(this: &RequestFilter, value: WindowId);
+}
+
+impl RequestFilter {
+    /// Matches requests whose URL matches one of the match patterns.
+    pub fn new<S: AsRef<str>>(urls: &[S]) -> Self {
+        let filter: Self = Object::new().unchecked_into();
+        filter.set_urls(
+            &urls
+                .iter()
+                .map(|url| JsValue::from_str(url.as_ref()))
+                .collect(),
+        );
+        filter
+    }
+
+    /// Only matches requests for these kinds of resources.
+    pub fn types(&mut self, value: &[ResourceType]) -> &mut Self {
+        self.set_types(&value.iter().cloned().map(JsValue::from).collect());
+        self
+    }
+
+    /// Only matches requests of this tab.
+    pub fn tab_id(&mut self, value: TabId) -> &mut Self {
+        self.set_tab_id(value);
+        self
+    }
+
+    /// Only matches requests of this window.
+    pub fn window_id(&mut self, value: WindowId) -> &mut Self {
+        self.set_window_id(value);
+        self
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#type-HttpHeaders
+    #[derive(Debug, Clone)]
+    pub type HttpHeader;
+
+    // Name of the HTTP header.
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &HttpHeader) -> String;
+
+    // Value of the HTTP header if it can be represented by UTF-8.
+    #[wasm_bindgen(method, getter)]
+    pub fn value(this: &HttpHeader) -> Option<String>;
+
+    // Value of the HTTP header if it cannot be represented by UTF-8, stored
+    // as individual byte values (0..255).
+    #[wasm_bindgen(method, getter, js_name = binaryValue)]
+    pub fn binary_value(this: &HttpHeader) -> Option<Array>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#type-UploadData
+    #[derive(Debug, Clone)]
+    pub type UploadData;
+
+    // An ArrayBuffer with a copy of the data.
+    #[wasm_bindgen(method, getter)]
+    pub fn bytes(this: &UploadData) -> Option<ArrayBuffer>;
+
+    // A string with the file's path and name.
+    #[wasm_bindgen(method, getter)]
+    pub fn file(this: &UploadData) -> Option<String>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#event-onBeforeRequest
+    /// The body of a request, included with [`ExtraInfoSpec::RequestBody`].
+    #[derive(Debug, Clone)]
+    pub type WebRequestBody;
+
+    // Errors when obtaining request body data.
+    #[wasm_bindgen(method, getter)]
+    pub fn error(this: &WebRequestBody) -> Option<String>;
+
+    // The form data as an object mapping each key to an array of values.
+    // Only set for POST requests with a parsable form body.
+    #[wasm_bindgen(method, getter, js_name = formData)]
+    pub fn form_data(this: &WebRequestBody) -> Option<Object>;
+
+    #[wasm_bindgen(method, getter, js_name = raw)]
+    fn raw_js(this: &WebRequestBody) -> Option<Array>;
+}
+
+impl WebRequestBody {
+    /// The raw request body, if it is not form data.
+    pub fn raw(&self) -> Option<Vec<UploadData>> {
+        self.raw_js()
+            .map(|raw| raw.iter().map(JsCast::unchecked_into).collect())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/#event-onBeforeRequest
+    /// The details passed to the listeners of all `webRequest` events.
+    ///
+    /// Fields that only some of the events report are optional.
+    #[derive(Debug, Clone)]
+    pub type RequestDetails;
+
+    // The ID of the request. Unique within a browser session, so it can be
+    // used to relate the events of the same request.
+    #[wasm_bindgen(method, getter, js_name = requestId)]
+    pub fn request_id(this: &RequestDetails) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &RequestDetails) -> String;
+
+    // Standard HTTP method.
+    #[wasm_bindgen(method, getter)]
+    pub fn method(this: &RequestDetails) -> String;
+
+    // 0 indicates that the request happens in the main frame.
+    #[wasm_bindgen(method, getter, js_name = frameId)]
+    pub fn frame_id(this: &RequestDetails) -> i32;
+
+    // The ID of the parent frame, or -1 if there is none.
+    #[wasm_bindgen(method, getter, js_name = parentFrameId)]
+    pub fn parent_frame_id(this: &RequestDetails) -> i32;
+
+    // The ID of the tab in which the request takes place, or -1 if it isn't related to a tab.
+    #[wasm_bindgen(method, getter, js_name = tabId)]
+    pub fn tab_id(this: &RequestDetails) -> TabId;
+
+    // The time when this event fired, in milliseconds since the epoch.
+    #[wasm_bindgen(method, getter, js_name = timeStamp)]
+    pub fn time_stamp(this: &RequestDetails) -> f64;
+
+    // The origin where the request was initiated (Chromium only).
+    #[wasm_bindgen(method, getter)]
+    pub fn initiator(this: &RequestDetails) -> Option<String>;
+
+    // URL of the resource that triggered the request (Firefox only).
+    #[wasm_bindgen(method, getter, js_name = originUrl)]
+    pub fn origin_url(this: &RequestDetails) -> Option<String>;
+
+    // URL of the document in which the resource will be loaded (Firefox only).
+    #[wasm_bindgen(method, getter, js_name = documentUrl)]
+    pub fn document_url(this: &RequestDetails) -> Option<String>;
+
+    // The UUID of the document making the request (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = documentId)]
+    pub fn document_id(this: &RequestDetails) -> Option<String>;
+
+    // The UUID of the parent document owning this frame (Chromium only).
+    #[wasm_bindgen(method, getter, js_name = parentDocumentId)]
+    pub fn parent_document_id(this: &RequestDetails) -> Option<String>;
+
+    // Only set by `onBeforeRequest` with `ExtraInfoSpec::RequestBody`.
+    #[wasm_bindgen(method, getter, js_name = requestBody)]
+    pub fn request_body(this: &RequestDetails) -> Option<WebRequestBody>;
+
+    // HTTP status line of the response.
+    #[wasm_bindgen(method, getter, js_name = statusLine)]
+    pub fn status_line(this: &RequestDetails) -> Option<String>;
+
+    // Standard HTTP status code returned by the server.
+    #[wasm_bindgen(method, getter, js_name = statusCode)]
+    pub fn status_code(this: &RequestDetails) -> Option<u16>;
+
+    // The server IP address that the request was actually sent to.
+    #[wasm_bindgen(method, getter)]
+    pub fn ip(this: &RequestDetails) -> Option<String>;
+
+    // Indicates if this response was fetched from disk cache.
+    #[wasm_bindgen(method, getter, js_name = fromCache)]
+    pub fn from_cache(this: &RequestDetails) -> Option<bool>;
+
+    // The new URL. Only set by `onBeforeRedirect`.
+    #[wasm_bindgen(method, getter, js_name = redirectUrl)]
+    pub fn redirect_url(this: &RequestDetails) -> Option<String>;
+
+    // The error description. Only set by `onErrorOccurred`.
+    #[wasm_bindgen(method, getter)]
+    pub fn error(this: &RequestDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = type)]
+    fn type_js(this: &RequestDetails) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = documentLifecycle)]
+    fn document_lifecycle_js(this: &RequestDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = frameType)]
+    fn frame_type_js(this: &RequestDetails) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = requestHeaders)]
+    fn request_headers_js(this: &RequestDetails) -> Option<Array>;
+
+    #[wasm_bindgen(method, getter, js_name = responseHeaders)]
+    fn response_headers_js(this: &RequestDetails) -> Option<Array>;
+}
+
+impl RequestDetails {
+    /// How the requested resource will be used.
+    pub fn type_(&self) -> ResourceType {
+        ResourceType::from(self.type_js())
+    }
+
+    /// The lifecycle the document is in (Chromium only).
+    pub fn document_lifecycle(&self) -> Option<DocumentLifecycle> {
+        self.document_lifecycle_js().map(DocumentLifecycle::from)
+    }
+
+    /// The type of frame the request occurred in (Chromium only).
+    pub fn frame_type(&self) -> Option<FrameType> {
+        self.frame_type_js().map(FrameType::from)
+    }
+
+    /// The request headers, included with [`ExtraInfoSpec::RequestHeaders`].
+    pub fn request_headers(&self) -> Option<Vec<HttpHeader>> {
+        self.request_headers_js()
+            .map(|headers| headers.iter().map(JsCast::unchecked_into).collect())
+    }
+
+    /// The response headers, included with [`ExtraInfoSpec::ResponseHeaders`].
+    pub fn response_headers(&self) -> Option<Vec<HttpHeader>> {
+        self.response_headers_js()
+            .map(|headers| headers.iter().map(JsCast::unchecked_into).collect())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/webRequest/
+    /// The `webRequest` events.
+    ///
+    /// Every event must be registered with
+    /// [`EventTarget::listen_with_options`] and a [`RequestFilter`]. The
+    /// listener receives [`RequestDetails`].
+    pub type WebRequest;
+
+    // Fired when a request is about to occur.
+    #[wasm_bindgen(method, getter, js_name = onBeforeRequest)]
+    pub fn on_before_request(this: &WebRequest) -> EventTarget;
+
+    // Fired before sending an HTTP request, once the request headers are available.
+    #[wasm_bindgen(method, getter, js_name = onBeforeSendHeaders)]
+    pub fn on_before_send_headers(this: &WebRequest) -> EventTarget;
+
+    // Fired just before a request is going to be sent to the server.
+    #[wasm_bindgen(method, getter, js_name = onSendHeaders)]
+    pub fn on_send_headers(this: &WebRequest) -> EventTarget;
+
+    // Fired when HTTP response headers of a request have been received.
+    #[wasm_bindgen(method, getter, js_name = onHeadersReceived)]
+    pub fn on_headers_received(this: &WebRequest) -> EventTarget;
+
+    // Fired when an authentication failure is received.
+    #[wasm_bindgen(method, getter, js_name = onAuthRequired)]
+    pub fn on_auth_required(this: &WebRequest) -> EventTarget;
+
+    // Fired when the first byte of the response body is received.
+    #[wasm_bindgen(method, getter, js_name = onResponseStarted)]
+    pub fn on_response_started(this: &WebRequest) -> EventTarget;
+
+    // Fired when a server-initiated redirect is about to occur.
+    #[wasm_bindgen(method, getter, js_name = onBeforeRedirect)]
+    pub fn on_before_redirect(this: &WebRequest) -> EventTarget;
+
+    // Fired when a request is completed.
+    #[wasm_bindgen(method, getter, js_name = onCompleted)]
+    pub fn on_completed(this: &WebRequest) -> EventTarget;
+
+    // Fired when an error occurs.
+    #[wasm_bindgen(method, getter, js_name = onErrorOccurred)]
+    pub fn on_error_occurred(this: &WebRequest) -> EventTarget;
+}
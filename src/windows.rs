@@ -2,6 +2,19 @@ use crate::EventTarget;
 use js_sys::{Array, Object};
 use wasm_bindgen::prelude::*;
 
+js_number_newtype! {
+    /// The ID of a browser window.
+    ///
+    /// Window IDs are unique within a browser session.
+    WindowId(i32)
+}
+
+/// The `windowId` value that represents the absence of a browser window.
+pub const WINDOW_ID_NONE: WindowId = WindowId(-1);
+
+/// The `windowId` value that represents the current window.
+pub const WINDOW_ID_CURRENT: WindowId = WindowId(-2);
+
 #[wasm_bindgen]
 extern "C" {
     #[derive(Debug)]
@@ -33,8 +46,7 @@ extern "C" {
     pub fn height(this: &Window) -> Option<u32>;
 
     #[wasm_bindgen(method, getter)]
-    // TODO is i32 correct ?
-    pub fn id(this: &Window) -> Option<i32>;
+    pub fn id(this: &Window) -> Option<WindowId>;
 
     #[wasm_bindgen(method, getter, js_name = sessionId)]
     pub fn session_id(this: &Window) -> Option<String>;
@@ -42,14 +54,50 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn title(this: &Window) -> Option<String>;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn state(this: &Window) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = state)]
+    fn state_js(this: &Window) -> Option<String>;
 
     #[wasm_bindgen(method, getter)]
     pub fn tabs(this: &Window) -> Option<Array>;
 
     #[wasm_bindgen(method, getter, js_name = type)]
-    pub fn type_(this: &Window) -> Option<String>;
+    fn type_js(this: &Window) -> Option<String>;
+}
+
+impl Window {
+    /// The state of this browser window.
+    pub fn state(&self) -> Option<WindowState> {
+        self.state_js().map(WindowState::from)
+    }
+
+    /// The type of browser window this is.
+    pub fn type_(&self) -> Option<WindowType> {
+        self.type_js().map(WindowType::from)
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/windows/#type-WindowState
+    /// The state of a browser window.
+    WindowState {
+        Normal = "normal",
+        Minimized = "minimized",
+        Maximized = "maximized",
+        Fullscreen = "fullscreen",
+        LockedFullscreen = "locked-fullscreen",
+    }
+}
+
+js_string_enum! {
+    // https://developer.chrome.com/docs/extensions/reference/windows/#type-WindowType
+    /// The type of a browser window.
+    WindowType {
+        Normal = "normal",
+        Popup = "popup",
+        Panel = "panel",
+        App = "app",
+        Devtools = "devtools",
+    }
 }
 
 #[wasm_bindgen]
@@ -57,13 +105,17 @@ extern "C" {
     pub type Windows;
 
     #[wasm_bindgen(method, getter, js_name = WINDOW_ID_NONE)]
-    pub fn window_id_none(this: &Windows) -> i32;
+    pub fn window_id_none(this: &Windows) -> WindowId;
 
     #[wasm_bindgen(method, getter, js_name = WINDOW_ID_CURRENT)]
-    pub fn window_id_current(this: &Windows) -> i32;
+    pub fn window_id_current(this: &Windows) -> WindowId;
 
     #[wasm_bindgen(catch, method)]
-    pub async fn get(this: &Windows, window_id: i32, info: &Object) -> Result<JsValue, JsValue>;
+    pub async fn get(
+        this: &Windows,
+        window_id: WindowId,
+        info: &Object,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method, js_name = getCurrent)]
     pub async fn get_current(this: &Windows, info: &Object) -> Result<JsValue, JsValue>;
@@ -78,10 +130,14 @@ extern "C" {
     pub async fn create(this: &Windows, info: &Object) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method)]
-    pub async fn update(this: &Windows, window_id: i32, info: &Object) -> Result<JsValue, JsValue>;
+    pub async fn update(
+        this: &Windows,
+        window_id: WindowId,
+        info: &Object,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch, method)]
-    pub async fn remove(this: &Windows, window_id: i32) -> Result<JsValue, JsValue>;
+    pub async fn remove(this: &Windows, window_id: WindowId) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(method, getter, js_name = onCreated)]
     pub fn on_created(this: &Windows) -> EventTarget;